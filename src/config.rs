@@ -1,5 +1,8 @@
-use anyhow::Result;
+use crate::parser::ParsedSchema;
+use anyhow::{bail, Context, Result};
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::Path;
 
@@ -18,6 +21,123 @@ pub struct Config {
     /// Enable verbose output during scanning
     #[serde(default)]
     pub verbose: bool,
+
+    /// Models to generate
+    #[serde(default)]
+    pub models: ModelSelection,
+
+    /// Operations to generate per model; models that are not listed get every operation
+    #[serde(default)]
+    pub operations: BTreeMap<String, Vec<Operation>>,
+
+    /// Use Relay connections (`@pothos/plugin-relay`) for findMany and list relations
+    #[serde(default)]
+    pub relay: bool,
+
+    /// Use `{Type}FieldUpdateOperationsInput` (`set`, `increment`...) for scalar fields of update inputs
+    #[serde(default)]
+    pub field_update_operations: bool,
+}
+
+/// `models: { include, exclude }`
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct ModelSelection {
+    /// Generate only these models (every model when empty)
+    #[serde(default)]
+    pub include: Vec<String>,
+
+    /// Never generate these models
+    #[serde(default)]
+    pub exclude: Vec<String>,
+}
+
+/// A generated query or mutation, or a group of them in `operations`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Operation {
+    CreateOne,
+    CreateMany,
+    FindMany,
+    FindUnique,
+    FindFirst,
+    FindFirstOrThrow,
+    FindUniqueOrThrow,
+    Aggregate,
+    GroupBy,
+    UpdateOne,
+    UpdateMany,
+    UpsertOne,
+    DeleteOne,
+    DeleteMany,
+    /// Every query
+    Queries,
+    /// Every mutation
+    Mutations,
+}
+
+impl Operation {
+    /// Every generated operation, in output order
+    pub const ALL: [Operation; 14] = [
+        Operation::CreateOne,
+        Operation::CreateMany,
+        Operation::FindMany,
+        Operation::FindUnique,
+        Operation::FindFirst,
+        Operation::FindFirstOrThrow,
+        Operation::FindUniqueOrThrow,
+        Operation::Aggregate,
+        Operation::GroupBy,
+        Operation::UpdateOne,
+        Operation::UpdateMany,
+        Operation::UpsertOne,
+        Operation::DeleteOne,
+        Operation::DeleteMany,
+    ];
+
+    /// Name used in `operations` and as the resolver file prefix, e.g. `createOne`
+    pub fn name(self) -> &'static str {
+        match self {
+            Operation::CreateOne => "createOne",
+            Operation::CreateMany => "createMany",
+            Operation::FindMany => "findMany",
+            Operation::FindUnique => "findUnique",
+            Operation::FindFirst => "findFirst",
+            Operation::FindFirstOrThrow => "findFirstOrThrow",
+            Operation::FindUniqueOrThrow => "findUniqueOrThrow",
+            Operation::Aggregate => "aggregate",
+            Operation::GroupBy => "groupBy",
+            Operation::UpdateOne => "updateOne",
+            Operation::UpdateMany => "updateMany",
+            Operation::UpsertOne => "upsertOne",
+            Operation::DeleteOne => "deleteOne",
+            Operation::DeleteMany => "deleteMany",
+            Operation::Queries => "queries",
+            Operation::Mutations => "mutations",
+        }
+    }
+
+    pub fn is_query(self) -> bool {
+        matches!(
+            self,
+            Operation::FindMany
+                | Operation::FindUnique
+                | Operation::FindFirst
+                | Operation::FindFirstOrThrow
+                | Operation::FindUniqueOrThrow
+                | Operation::Aggregate
+                | Operation::GroupBy
+                | Operation::Queries
+        )
+    }
+
+    /// Whether this entry of an `operations` list selects `operation`
+    fn selects(self, operation: Operation) -> bool {
+        match self {
+            Operation::Queries => operation.is_query(),
+            Operation::Mutations => !operation.is_query(),
+            other => other == operation,
+        }
+    }
 }
 
 fn default_true() -> bool {
@@ -30,6 +150,10 @@ impl Default for Config {
             auto_scan: true,
             scan_dirs: Vec::new(),
             verbose: false,
+            models: ModelSelection::default(),
+            operations: BTreeMap::new(),
+            relay: false,
+            field_update_operations: false,
         }
     }
 }
@@ -49,4 +173,76 @@ impl Config {
         
         Ok(config)
     }
+
+    /// Whether a model passes `models.include` and `models.exclude`
+    pub fn includes_model(&self, model: &str) -> bool {
+        let included = self.models.include.is_empty() || self.models.include.iter().any(|m| m == model);
+        included && !self.models.exclude.iter().any(|m| m == model)
+    }
+
+    /// Whether `operations` enables an operation for a model
+    pub fn operation_enabled(&self, model: &str, operation: Operation) -> bool {
+        match self.operations.get(model) {
+            Some(selected) => selected.iter().any(|s| s.selects(operation)),
+            None => true,
+        }
+    }
+
+    /// Fail on model names in `models` or `operations` that the schema does not define
+    pub fn check_models(&self, schema: &ParsedSchema) -> Result<()> {
+        let configured = self
+            .models
+            .include
+            .iter()
+            .map(|m| ("models.include", m))
+            .chain(self.models.exclude.iter().map(|m| ("models.exclude", m)))
+            .chain(self.operations.keys().map(|m| ("operations", m)));
+
+        for (option, model) in configured {
+            if !schema.models.iter().any(|m| &m.name == model) {
+                bail!("unknown model `{}` in `{}`", model, option);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Apply the options of the schema's `generator` block for this generator
+    /// (the one whose provider mentions `gpothos`), which take precedence over .gpothosrc.json
+    pub fn merge_schema_generator(&mut self, schema: &ParsedSchema) -> Result<()> {
+        let generator = schema
+            .generators
+            .iter()
+            .find(|g| g.provider.as_deref().is_some_and(|p| p.contains("gpothos")));
+
+        match generator {
+            Some(generator) => self
+                .merge_options(&generator.config)
+                .with_context(|| format!("invalid option in generator `{}`", generator.name)),
+            None => Ok(()),
+        }
+    }
+
+    /// Override fields with generator options
+    /// Prisma passes options as strings or lists of strings, so strings such as `"true"` are read as JSON
+    pub fn merge_options(&mut self, options: &Map<String, Value>) -> Result<()> {
+        let mut merged = serde_json::to_value(&*self)?;
+        if let Value::Object(fields) = &mut merged {
+            for (name, value) in options {
+                if !value.is_null() {
+                    fields.insert(name.clone(), option_value(value));
+                }
+            }
+        }
+
+        *self = serde_json::from_value(merged)?;
+        Ok(())
+    }
+}
+
+fn option_value(value: &Value) -> Value {
+    match value {
+        Value::String(text) => serde_json::from_str(text).unwrap_or_else(|_| value.clone()),
+        other => other.clone(),
+    }
 }
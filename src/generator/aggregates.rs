@@ -0,0 +1,166 @@
+use crate::generator::get_prisma_name;
+use crate::parser::{Field, FieldType, Model};
+use anyhow::Result;
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+
+/// Generate `{Model}CountAggregate`, `{Model}SumAggregate`, `{Model}AvgAggregate`,
+/// `{Model}MinAggregate` and `{Model}MaxAggregate`, and the `{Model}GroupBy` row type
+pub fn generate_aggregates(model: &Model, output_dir: &Path) -> Result<()> {
+    let models_dir = output_dir.join("models");
+    fs::create_dir_all(&models_dir)?;
+
+    let names = get_prisma_name(&model.name);
+    let fields = aggregated_fields(model);
+
+    let mut body = String::new();
+
+    // _count is available for every scalar field, lists included
+    let count: Vec<(&str, String)> = fields
+        .iter()
+        .map(|f| (f.name.as_str(), "t.int({ nullable: false })".to_string()))
+        .chain([("_all", "t.int({ nullable: false })".to_string())])
+        .collect();
+    write_object(&mut body, &names.count_aggregate, &count);
+
+    let single: Vec<&Field> = fields.into_iter().filter(|f| !f.is_list).collect();
+    let sections = [
+        (&names.sum_aggregate, collect(&single, sum_field)),
+        (&names.avg_aggregate, collect(&single, avg_field)),
+        (&names.min_aggregate, collect(&single, min_max_field)),
+        (&names.max_aggregate, collect(&single, min_max_field)),
+    ];
+    for (name, fields) in &sections {
+        if !fields.is_empty() {
+            write_object(&mut body, name, fields);
+        }
+    }
+
+    // One row of groupBy: the grouped fields and the aggregates of the group
+    let mut group_by: Vec<(&str, String)> = aggregated_fields(model)
+        .into_iter()
+        .map(|f| (f.name.as_str(), group_by_field(f)))
+        .collect();
+    for (key, type_name) in &aggregate_types(model) {
+        group_by.push((key, format!("t.field({{ type: {}, nullable: true }})", type_name)));
+    }
+    write_object(&mut body, &names.group_by_output, &group_by);
+
+    // Enums used by _min/_max and groupBy
+    let used_enums: BTreeSet<&str> = aggregated_fields(model)
+        .into_iter()
+        .filter_map(|f| match &f.field_type {
+            FieldType::Enum(enum_name) => Some(enum_name.as_str()),
+            _ => None,
+        })
+        .collect();
+
+    let mut content = String::from("import { builder } from \"../builder\";\n");
+    if !used_enums.is_empty() {
+        let enum_imports: Vec<&str> = used_enums.into_iter().collect();
+        content.push_str(&format!("import {{ {} }} from \"../enums\";\n", enum_imports.join(", ")));
+    }
+    content.push_str(&body);
+
+    fs::write(models_dir.join(format!("{}Aggregates.ts", model.name)), content)?;
+
+    Ok(())
+}
+
+/// Aggregate result fields (`_count`, `_sum`...) of a model with the type of each one
+/// `_sum` and `_avg` are left out when the model has no numeric fields
+pub fn aggregate_types(model: &Model) -> Vec<(&'static str, String)> {
+    let names = get_prisma_name(&model.name);
+    let single: Vec<&Field> = aggregated_fields(model).into_iter().filter(|f| !f.is_list).collect();
+
+    let mut types = vec![("_count", names.count_aggregate)];
+    if !collect(&single, sum_field).is_empty() {
+        types.push(("_sum", names.sum_aggregate));
+    }
+    if !collect(&single, avg_field).is_empty() {
+        types.push(("_avg", names.avg_aggregate));
+    }
+    if !collect(&single, min_max_field).is_empty() {
+        types.push(("_min", names.min_aggregate));
+        types.push(("_max", names.max_aggregate));
+    }
+    types
+}
+
+/// Scalar fields that can be grouped, aggregated and picked in `distinct`: they must be
+/// visible in the object type and filterable, since `by` and `having` refer to them
+pub fn is_groupable(field: &Field) -> bool {
+    field.field_type.is_scalar() && !field.directives.omit_output && !field.directives.omit_where
+}
+
+fn aggregated_fields(model: &Model) -> Vec<&Field> {
+    model.fields.iter().filter(|f| is_groupable(f)).collect()
+}
+
+fn collect<'a>(fields: &[&'a Field], field_code: fn(&Field) -> Option<String>) -> Vec<(&'a str, String)> {
+    fields
+        .iter()
+        .filter_map(|f| field_code(f).map(|code| (f.name.as_str(), code)))
+        .collect()
+}
+
+/// `_sum` keeps the field type
+pub fn sum_field(field: &Field) -> Option<String> {
+    match field.field_type {
+        FieldType::Int => Some("t.int({ nullable: true })".to_string()),
+        FieldType::Float => Some("t.float({ nullable: true })".to_string()),
+        FieldType::Decimal => Some("t.field({ type: \"Decimal\", nullable: true })".to_string()),
+        FieldType::BigInt => Some("t.field({ type: \"BigInt\", nullable: true })".to_string()),
+        _ => None,
+    }
+}
+
+/// `_avg` is a Float, except for Decimal which keeps its precision
+fn avg_field(field: &Field) -> Option<String> {
+    match field.field_type {
+        FieldType::Int | FieldType::Float | FieldType::BigInt => Some("t.float({ nullable: true })".to_string()),
+        FieldType::Decimal => Some("t.field({ type: \"Decimal\", nullable: true })".to_string()),
+        _ => None,
+    }
+}
+
+/// `_min` and `_max` work on every comparable field
+pub fn min_max_field(field: &Field) -> Option<String> {
+    match &field.field_type {
+        FieldType::String => Some("t.string({ nullable: true })".to_string()),
+        FieldType::Boolean => Some("t.boolean({ nullable: true })".to_string()),
+        FieldType::DateTime => Some("t.field({ type: \"DateTime\", nullable: true })".to_string()),
+        FieldType::Enum(enum_name) => Some(format!("t.field({{ type: {}, nullable: true }})", enum_name)),
+        _ => sum_field(field),
+    }
+}
+
+/// Grouped fields are only present when listed in `by`, so they are all nullable
+fn group_by_field(field: &Field) -> String {
+    let type_name = match &field.field_type {
+        FieldType::String => "\"String\"",
+        FieldType::Int => "\"Int\"",
+        FieldType::Float => "\"Float\"",
+        FieldType::Boolean => "\"Boolean\"",
+        FieldType::DateTime => "\"DateTime\"",
+        FieldType::Decimal => "\"Decimal\"",
+        FieldType::BigInt => "\"BigInt\"",
+        FieldType::Bytes => "\"Bytes\"",
+        FieldType::Enum(enum_name) => enum_name,
+        FieldType::Json | FieldType::Model(_) => "\"JSON\"",
+    };
+    let type_ref = if field.is_list { format!("[{}]", type_name) } else { type_name.to_string() };
+    format!("t.field({{ type: {}, nullable: true }})", type_ref)
+}
+
+fn write_object(content: &mut String, name: &str, fields: &[(&str, String)]) {
+    content.push_str(&format!(
+        "\nexport const {} = builder.simpleObject(\"{}\", {{\n  fields: (t) => ({{\n",
+        name, name
+    ));
+    for (field_name, code) in fields {
+        content.push_str(&format!("    {}: {},\n", field_name, code));
+    }
+    content.push_str("  }),\n});\n");
+}
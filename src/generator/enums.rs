@@ -1,14 +1,17 @@
+use crate::generator::aggregates::is_groupable;
+use crate::generator::helpers::{get_prisma_name, js_string};
 use crate::parser::ParsedSchema;
 use anyhow::Result;
 use std::fs;
 use std::path::Path;
 
-/// Generate base enums for Pothos (SortOrder, QueryMode, NullsOrder)
+/// Generate base enums for Pothos (SortOrder, QueryMode, NullsOrder and the Json null sentinels)
 pub fn generate_base_enums(output_dir: &Path) -> Result<()> {
     let enums_dir = output_dir.join("enums");
     fs::create_dir_all(&enums_dir)?;
 
-    let content = r#"import { builder } from "../builder";
+    let content = r#"import { Prisma } from "@prisma/client";
+import { builder } from "../builder";
 
 // SortOrder enum
 export const SortOrder = builder.enumType("SortOrder", {
@@ -24,6 +27,23 @@ export const NullsOrder = builder.enumType("NullsOrder", {
 export const QueryMode = builder.enumType("QueryMode", {
   values: ["default", "insensitive"] as const,
 });
+
+// Json nulls in create and update inputs: DbNull (database NULL) or JsonNull (JSON null)
+export const NullableJsonNullValueInput = builder.enumType("NullableJsonNullValueInput", {
+  values: {
+    DbNull: { value: Prisma.DbNull },
+    JsonNull: { value: Prisma.JsonNull },
+  } as const,
+});
+
+// Json nulls in filters, where AnyNull matches both
+export const JsonNullValueFilter = builder.enumType("JsonNullValueFilter", {
+  values: {
+    DbNull: { value: Prisma.DbNull },
+    JsonNull: { value: Prisma.JsonNull },
+    AnyNull: { value: Prisma.AnyNull },
+  } as const,
+});
 "#;
 
     fs::write(enums_dir.join("index.ts"), content)?;
@@ -40,18 +60,60 @@ pub fn generate_schema_enums(schema: &ParsedSchema, output_dir: &Path) -> Result
 
     for enum_def in &schema.enums {
         content.push_str(&format!(
-            "\n// {} enum\nexport const {} = builder.enumType(\"{}\", {{\n  values: [",
+            "\n// {} enum\nexport const {} = builder.enumType(\"{}\", {{\n",
             enum_def.name, enum_def.name, enum_def.name
         ));
 
-        let values: Vec<String> = enum_def
-            .values
+        // Descriptions need the object form of `values`
+        if enum_def.values.iter().any(|v| v.documentation.is_some()) {
+            content.push_str("  values: {\n");
+            for value in &enum_def.values {
+                match &value.documentation {
+                    Some(description) => content.push_str(&format!(
+                        "    {}: {{ description: {} }},\n",
+                        value.name,
+                        js_string(description)
+                    )),
+                    None => content.push_str(&format!("    {}: {{}},\n", value.name)),
+                }
+            }
+            content.push_str("  } as const,\n});\n");
+        } else {
+            let values: Vec<String> = enum_def
+                .values
+                .iter()
+                .map(|v| format!("\"{}\"", v.name))
+                .collect();
+            content.push_str(&format!("  values: [{}] as const,\n}});\n", values.join(", ")));
+        }
+    }
+
+    fs::write(enums_dir.join("index.ts"), content)?;
+
+    Ok(())
+}
+
+/// Generate `{Model}ScalarFieldEnum`, the type of the `distinct` argument
+pub fn generate_scalar_field_enums(schema: &ParsedSchema, output_dir: &Path) -> Result<()> {
+    let enums_dir = output_dir.join("enums");
+    let mut content = fs::read_to_string(enums_dir.join("index.ts")).unwrap_or_default();
+
+    for model in &schema.models {
+        let enum_name = get_prisma_name(&model.name).scalar_field_enum;
+        let values: Vec<String> = model
+            .fields
             .iter()
-            .map(|v| format!("\"{}\"", v.name))
+            .filter(|f| is_groupable(f))
+            .map(|f| format!("\"{}\"", f.name))
             .collect();
-        content.push_str(&values.join(", "));
 
-        content.push_str("] as const,\n});\n");
+        content.push_str(&format!(
+            "\n// {} scalar fields\nexport const {} = builder.enumType(\"{}\", {{\n  values: [{}] as const,\n}});\n",
+            model.name,
+            enum_name,
+            enum_name,
+            values.join(", ")
+        ));
     }
 
     fs::write(enums_dir.join("index.ts"), content)?;
@@ -1,152 +1,420 @@
+use crate::parser::{ParsedSchema, Provider};
 use anyhow::Result;
 use std::fs;
 use std::path::Path;
 
+/// A scalar filter (`StringFilter`, `IntFilter`...) and the operators it supports
+#[derive(Clone, Copy)]
+struct ScalarFilter<'a> {
+    /// Prefix of the input names, e.g. `String` for `StringFilter`
+    name: &'a str,
+    /// Pothos field for a single value
+    value: &'a str,
+    /// Pothos field for a list of values
+    list: &'a str,
+    /// `in` and `notIn`
+    membership: bool,
+    /// `lt`, `lte`, `gt` and `gte`
+    comparable: bool,
+    /// `contains`, `startsWith` and `endsWith`
+    text: bool,
+    /// `_sum` and `_avg` in the WithAggregates variant, with the filter used for `_avg`
+    average: Option<&'a str>,
+}
+
+const SCALAR_FILTERS: [ScalarFilter<'static>; 8] = [
+    ScalarFilter {
+        name: "String",
+        value: "t.string()",
+        list: "t.stringList()",
+        membership: true,
+        comparable: true,
+        text: true,
+        average: None,
+    },
+    ScalarFilter {
+        name: "Int",
+        value: "t.int()",
+        list: "t.intList()",
+        membership: true,
+        comparable: true,
+        text: false,
+        average: Some("Float"),
+    },
+    ScalarFilter {
+        name: "Float",
+        value: "t.float()",
+        list: "t.field({ type: [\"Float\"] })",
+        membership: true,
+        comparable: true,
+        text: false,
+        average: Some("Float"),
+    },
+    ScalarFilter {
+        name: "Bool",
+        value: "t.boolean()",
+        list: "t.booleanList()",
+        membership: false,
+        comparable: false,
+        text: false,
+        average: None,
+    },
+    ScalarFilter {
+        name: "DateTime",
+        value: "t.field({ type: \"DateTime\" })",
+        list: "t.field({ type: [\"DateTime\"] })",
+        membership: true,
+        comparable: true,
+        text: false,
+        average: None,
+    },
+    ScalarFilter {
+        name: "Decimal",
+        value: "t.field({ type: \"Decimal\" })",
+        list: "t.field({ type: [\"Decimal\"] })",
+        membership: true,
+        comparable: true,
+        text: false,
+        average: Some("Decimal"),
+    },
+    ScalarFilter {
+        name: "BigInt",
+        value: "t.field({ type: \"BigInt\" })",
+        list: "t.field({ type: [\"BigInt\"] })",
+        membership: true,
+        comparable: true,
+        text: false,
+        average: Some("Float"),
+    },
+    ScalarFilter {
+        name: "Bytes",
+        value: "t.field({ type: \"Bytes\" })",
+        list: "t.field({ type: [\"Bytes\"] })",
+        membership: true,
+        comparable: false,
+        text: false,
+        average: None,
+    },
+];
+
+/// Json has its own filters, except for lists which work like any other scalar
+const JSON_LIST_FILTER: ScalarFilter<'static> = ScalarFilter {
+    name: "Json",
+    value: "t.field({ type: \"JSON\" })",
+    list: "t.field({ type: [\"JSON\"] })",
+    membership: false,
+    comparable: false,
+    text: false,
+    average: None,
+};
+
+/// One member of a filter family, e.g. `NestedIntNullableWithAggregatesFilter`
+#[derive(Clone, Copy)]
+struct Variant {
+    /// `Nested...`, the type of `not`
+    nested: bool,
+    /// `...Nullable...`, for optional fields
+    nullable: bool,
+    /// `...WithAggregates...`, for `having` in groupBy
+    aggregates: bool,
+}
+
+/// Name of a filter input, e.g. `IntNullableWithAggregatesFilter`, following Prisma's names
+pub fn filter_name(base: &str, nullable: bool, aggregates: bool) -> String {
+    format!(
+        "{}{}{}Filter",
+        base,
+        if nullable { "Nullable" } else { "" },
+        if aggregates { "WithAggregates" } else { "" }
+    )
+}
+
 /// Generate all base filter input types for Pothos
-pub fn generate_filters(output_dir: &Path) -> Result<()> {
+pub fn generate_filters(schema: &ParsedSchema, output_dir: &Path) -> Result<()> {
     let inputs_dir = output_dir.join("inputs");
     fs::create_dir_all(&inputs_dir)?;
 
-    let content = r#"import { builder } from "../builder";
-
-// Nested String Filter (for use inside `not`)
-export const NestedStringFilter = builder.inputType("NestedStringFilter", {
-  fields: (t) => ({
-    equals: t.string(),
-    in: t.stringList(),
-    notIn: t.stringList(),
-    lt: t.string(),
-    lte: t.string(),
-    gt: t.string(),
-    gte: t.string(),
-    contains: t.string(),
-    startsWith: t.string(),
-    endsWith: t.string(),
-    mode: t.string(),
-    not: t.field({ type: NestedStringFilter }),
-  }),
-});
-
-// String Filter
-export const StringFilter = builder.inputType("StringFilter", {
-  fields: (t) => ({
-    equals: t.string(),
-    in: t.stringList(),
-    notIn: t.stringList(),
-    lt: t.string(),
-    lte: t.string(),
-    gt: t.string(),
-    gte: t.string(),
-    contains: t.string(),
-    startsWith: t.string(),
-    endsWith: t.string(),
-    mode: t.string(),
-    not: t.field({ type: NestedStringFilter }),
-  }),
-});
-
-// Nested Int Filter (for use inside `not`)
-export const NestedIntFilter = builder.inputType("NestedIntFilter", {
-  fields: (t) => ({
-    equals: t.int(),
-    in: t.intList(),
-    notIn: t.intList(),
-    lt: t.int(),
-    lte: t.int(),
-    gt: t.int(),
-    gte: t.int(),
-    not: t.field({ type: NestedIntFilter }),
-  }),
-});
-
-// Int Filter
-export const IntFilter = builder.inputType("IntFilter", {
-  fields: (t) => ({
-    equals: t.int(),
-    in: t.intList(),
-    notIn: t.intList(),
-    lt: t.int(),
-    lte: t.int(),
-    gt: t.int(),
-    gte: t.int(),
-    not: t.field({ type: NestedIntFilter }),
-  }),
-});
-
-// Nested Float Filter (for use inside `not`)
-export const NestedFloatFilter = builder.inputType("NestedFloatFilter", {
-  fields: (t) => ({
-    equals: t.float(),
-    in: t.field({ type: ["Float"] }),
-    notIn: t.field({ type: ["Float"] }),
-    lt: t.float(),
-    lte: t.float(),
-    gt: t.float(),
-    gte: t.float(),
-    not: t.field({ type: NestedFloatFilter }),
-  }),
-});
-
-// Float Filter
-export const FloatFilter = builder.inputType("FloatFilter", {
-  fields: (t) => ({
-    equals: t.float(),
-    in: t.field({ type: ["Float"] }),
-    notIn: t.field({ type: ["Float"] }),
-    lt: t.float(),
-    lte: t.float(),
-    gt: t.float(),
-    gte: t.float(),
-    not: t.field({ type: NestedFloatFilter }),
-  }),
-});
-
-// Nested Bool Filter (for use inside `not`)
-export const NestedBoolFilter = builder.inputType("NestedBoolFilter", {
-  fields: (t) => ({
-    equals: t.boolean(),
-    not: t.field({ type: NestedBoolFilter }),
-  }),
-});
-
-// Bool Filter
-export const BoolFilter = builder.inputType("BoolFilter", {
-  fields: (t) => ({
-    equals: t.boolean(),
-    not: t.field({ type: NestedBoolFilter }),
-  }),
-});
-
-// Nested DateTime Filter (for use inside `not`)
-export const NestedDateTimeFilter = builder.inputType("NestedDateTimeFilter", {
-  fields: (t) => ({
-    equals: t.field({ type: "DateTime" }),
-    in: t.field({ type: ["DateTime"] }),
-    notIn: t.field({ type: ["DateTime"] }),
-    lt: t.field({ type: "DateTime" }),
-    lte: t.field({ type: "DateTime" }),
-    gt: t.field({ type: "DateTime" }),
-    gte: t.field({ type: "DateTime" }),
-    not: t.field({ type: NestedDateTimeFilter }),
-  }),
-});
-
-// DateTime Filter
-export const DateTimeFilter = builder.inputType("DateTimeFilter", {
-  fields: (t) => ({
-    equals: t.field({ type: "DateTime" }),
-    in: t.field({ type: ["DateTime"] }),
-    notIn: t.field({ type: ["DateTime"] }),
-    lt: t.field({ type: "DateTime" }),
-    lte: t.field({ type: "DateTime" }),
-    gt: t.field({ type: "DateTime" }),
-    gte: t.field({ type: "DateTime" }),
-    not: t.field({ type: NestedDateTimeFilter }),
-  }),
-});
-"#;
+    // `mode: insensitive` is rejected by Prisma on MySQL, SQLite and SQL Server
+    let insensitive_mode = schema.supports(Provider::supports_insensitive_mode);
+    // `isSet` tells missing fields from explicit nulls, which only exist in MongoDB documents
+    let is_set = schema.supports(Provider::supports_is_set);
+
+    let enum_filters = enum_filters(schema);
+    let filters = with_enums(&enum_filters);
+
+    // `nulls: first | last` in orderBy is only understood by relational databases
+    let nulls_order = schema.supports(Provider::supports_nulls_order);
+
+    let mut content = String::from("import { builder } from \"../builder\";\n");
+    // Json filters are always written
+    let mut enum_imports: Vec<&str> = vec!["JsonNullValueFilter"];
+    if nulls_order {
+        enum_imports.push("NullsOrder");
+    }
+    if insensitive_mode {
+        enum_imports.push("QueryMode");
+    }
+    if nulls_order {
+        enum_imports.push("SortOrder");
+    }
+    enum_imports.extend(schema.enums.iter().map(|e| e.name.as_str()));
+    content.push_str(&format!("import {{ {} }} from \"../enums\";\n", enum_imports.join(", ")));
+
+    // Sort order of nullable fields in orderBy
+    if nulls_order {
+        content.push_str("\n// Sort Order Input (for nullable fields in orderBy)\n");
+        content.push_str("export const SortOrderInput = builder.inputType(\"SortOrderInput\", {\n  fields: (t) => ({\n");
+        content.push_str("    sort: t.field({ type: SortOrder, required: true }),\n");
+        content.push_str("    nulls: t.field({ type: NullsOrder }),\n");
+        content.push_str("  }),\n});\n");
+    }
+
+    // Plain filters, then the ones for `having` in groupBy, which can also filter on aggregates
+    for aggregates in [false, true] {
+        for nullable in [false, true] {
+            for filter in &filters {
+                for nested in [true, false] {
+                    let variant = Variant {
+                        nested,
+                        nullable,
+                        aggregates,
+                    };
+                    let mode = insensitive_mode && filter.text && !nested;
+                    write_filter(&mut content, filter, variant, mode, is_set && nullable);
+                }
+            }
+        }
+    }
+
+    // Json filters; path queries depend on the provider
+    let json_path = schema
+        .supports(Provider::supports_json_filters)
+        .then(|| schema.supports(Provider::supports_json_path_list));
+    for aggregates in [false, true] {
+        for nullable in [false, true] {
+            let mode = insensitive_mode && json_path.is_some();
+            write_json_filter(&mut content, nullable, aggregates, json_path, mode, is_set && nullable);
+        }
+    }
+
+    // Filters for list fields, which only exist where scalar lists do
+    if schema.supports(Provider::supports_scalar_lists) {
+        for filter in &filters {
+            write_list_filter(&mut content, filter);
+        }
+        write_list_filter(&mut content, &JSON_LIST_FILTER);
+    }
 
     fs::write(inputs_dir.join("filters.ts"), content)?;
 
     Ok(())
 }
+
+/// Name of a field update input, e.g. `NullableIntFieldUpdateOperationsInput`, following Prisma's names
+pub fn update_operations_name(base: &str, nullable: bool) -> String {
+    format!("{}{}FieldUpdateOperationsInput", if nullable { "Nullable" } else { "" }, base)
+}
+
+/// Generate `inputs/updateOperations.ts` with the `{Type}FieldUpdateOperationsInput` inputs,
+/// which let update inputs `set` a value or change numbers atomically (`increment`, `divide`...)
+pub fn generate_update_operations(schema: &ParsedSchema, output_dir: &Path) -> Result<()> {
+    let inputs_dir = output_dir.join("inputs");
+    fs::create_dir_all(&inputs_dir)?;
+
+    // `unset` removes the field from the document, which only exists in MongoDB
+    let unset = schema.supports(Provider::supports_is_set);
+
+    let enum_filters = enum_filters(schema);
+    let filters = with_enums(&enum_filters);
+
+    let mut content = String::from("import { builder } from \"../builder\";\n");
+    if !schema.enums.is_empty() {
+        let enum_imports: Vec<&str> = schema.enums.iter().map(|e| e.name.as_str()).collect();
+        content.push_str(&format!("import {{ {} }} from \"../enums\";\n", enum_imports.join(", ")));
+    }
+
+    for nullable in [false, true] {
+        for filter in &filters {
+            let input_name = update_operations_name(filter.name, nullable);
+            content.push_str(&format!("\n// {} Field Update Operations\n", describe(filter.name, nullable, false)));
+            content.push_str(&format!(
+                "export const {} = builder.inputType(\"{}\", {{\n  fields: (t) => ({{\n",
+                input_name, input_name
+            ));
+            content.push_str(&format!("    set: {},\n", filter.value));
+            // Numbers are the types with `_avg`
+            if filter.average.is_some() {
+                for op in ["increment", "decrement", "multiply", "divide"] {
+                    content.push_str(&format!("    {}: {},\n", op, filter.value));
+                }
+            }
+            if unset && nullable {
+                content.push_str("    unset: t.boolean(),\n");
+            }
+            content.push_str("  }),\n});\n");
+        }
+    }
+
+    fs::write(inputs_dir.join("updateOperations.ts"), content)?;
+
+    Ok(())
+}
+
+/// Name, single value and list fields of the filters of each schema enum
+fn enum_filters(schema: &ParsedSchema) -> Vec<(String, String, String)> {
+    schema
+        .enums
+        .iter()
+        .map(|e| {
+            (
+                format!("Enum{}", e.name),
+                format!("t.field({{ type: {} }})", e.name),
+                format!("t.field({{ type: [{}] }})", e.name),
+            )
+        })
+        .collect()
+}
+
+/// The scalar filters followed by the enum ones, which only allow equality
+fn with_enums(enum_filters: &[(String, String, String)]) -> Vec<ScalarFilter<'_>> {
+    SCALAR_FILTERS
+        .into_iter()
+        .chain(enum_filters.iter().map(|(name, value, list)| ScalarFilter {
+            name,
+            value,
+            list,
+            membership: true,
+            comparable: false,
+            text: false,
+            average: None,
+        }))
+        .collect()
+}
+
+/// Write `JsonFilter` or one of its Nullable/WithAggregates variants
+/// `path` is `None` when the provider has no JSON path queries, otherwise whether it takes a list of keys
+fn write_json_filter(content: &mut String, nullable: bool, aggregates: bool, path: Option<bool>, mode: bool, is_set: bool) {
+    let input_name = filter_name("Json", nullable, aggregates);
+
+    content.push_str(&format!("\n// {} Filter\n", describe("Json", nullable, aggregates)));
+    content.push_str(&format!(
+        "export const {} = builder.inputType(\"{}\", {{\n  fields: (t) => ({{\n",
+        input_name, input_name
+    ));
+
+    // A JSON string can be "DbNull", so the null sentinels go in `equalsNull` and `notNull`,
+    // which `withJsonNulls` moves to `equals` and `not`
+    content.push_str("    equals: t.field({ type: \"JSON\" }),\n");
+    content.push_str("    equalsNull: t.field({ type: JsonNullValueFilter }),\n");
+    if let Some(list) = path {
+        let path_field = if list { "t.stringList()" } else { "t.string()" };
+        content.push_str(&format!("    path: {},\n", path_field));
+        if mode {
+            content.push_str("    mode: t.field({ type: QueryMode }),\n");
+        }
+        for op in ["string_contains", "string_starts_with", "string_ends_with"] {
+            content.push_str(&format!("    {}: t.string(),\n", op));
+        }
+        for op in ["array_contains", "array_starts_with", "array_ends_with", "lt", "lte", "gt", "gte"] {
+            content.push_str(&format!("    {}: t.field({{ type: \"JSON\" }}),\n", op));
+        }
+    }
+    content.push_str("    not: t.field({ type: \"JSON\" }),\n");
+    content.push_str("    notNull: t.field({ type: JsonNullValueFilter }),\n");
+    if is_set {
+        content.push_str("    isSet: t.boolean(),\n");
+    }
+    if aggregates {
+        content.push_str(&format!("    _count: t.field({{ type: Nested{} }}),\n", filter_name("Int", false, false)));
+        content.push_str(&format!("    _min: t.field({{ type: {} }}),\n", filter_name("Json", nullable, false)));
+        content.push_str(&format!("    _max: t.field({{ type: {} }}),\n", filter_name("Json", nullable, false)));
+    }
+
+    content.push_str("  }),\n});\n");
+}
+
+/// `String Nullable WithAggregates`, used in the comment above each filter
+fn describe(base: &str, nullable: bool, aggregates: bool) -> String {
+    let mut description = base.to_string();
+    if nullable {
+        description.push_str(" Nullable");
+    }
+    if aggregates {
+        description.push_str(" WithAggregates");
+    }
+    description
+}
+
+/// Write `{Name}NullableListFilter`, for list fields
+fn write_list_filter(content: &mut String, filter: &ScalarFilter) {
+    let input_name = format!("{}NullableListFilter", filter.name);
+
+    content.push_str(&format!("\n// {} List Filter\n", filter.name));
+    content.push_str(&format!(
+        "export const {} = builder.inputType(\"{}\", {{\n  fields: (t) => ({{\n",
+        input_name, input_name
+    ));
+    content.push_str(&format!("    equals: {},\n", filter.list));
+    content.push_str(&format!("    has: {},\n", filter.value));
+    content.push_str(&format!("    hasEvery: {},\n", filter.list));
+    content.push_str(&format!("    hasSome: {},\n", filter.list));
+    content.push_str("    isEmpty: t.boolean(),\n");
+    content.push_str("  }),\n});\n");
+}
+
+/// Write one filter input; the nested variant is the type of `not`
+fn write_filter(content: &mut String, filter: &ScalarFilter, variant: Variant, mode: bool, is_set: bool) {
+    let nested = |base: &str, nullable: bool, aggregates: bool| format!("Nested{}", filter_name(base, nullable, aggregates));
+    let name = filter_name(filter.name, variant.nullable, variant.aggregates);
+    let input_name = if variant.nested { format!("Nested{}", name) } else { name };
+
+    let comment = describe(filter.name, variant.nullable, variant.aggregates);
+    if variant.nested {
+        content.push_str(&format!("\n// Nested {} Filter (for use inside `not`)\n", comment));
+    } else {
+        content.push_str(&format!("\n// {} Filter\n", comment));
+    }
+    content.push_str(&format!(
+        "export const {} = builder.inputType(\"{}\", {{\n  fields: (t) => ({{\n",
+        input_name, input_name
+    ));
+
+    content.push_str(&format!("    equals: {},\n", filter.value));
+    if filter.membership {
+        content.push_str(&format!("    in: {},\n", filter.list));
+        content.push_str(&format!("    notIn: {},\n", filter.list));
+    }
+    if filter.comparable {
+        for op in ["lt", "lte", "gt", "gte"] {
+            content.push_str(&format!("    {}: {},\n", op, filter.value));
+        }
+    }
+    if filter.text {
+        for op in ["contains", "startsWith", "endsWith"] {
+            content.push_str(&format!("    {}: {},\n", op, filter.value));
+        }
+    }
+    if mode {
+        content.push_str("    mode: t.field({ type: QueryMode }),\n");
+    }
+    content.push_str(&format!(
+        "    not: t.field({{ type: {} }}),\n",
+        nested(filter.name, variant.nullable, variant.aggregates)
+    ));
+    if is_set {
+        content.push_str("    isSet: t.boolean(),\n");
+    }
+    if variant.aggregates {
+        let nullable = variant.nullable;
+        // A count is never null, even over a nullable field
+        content.push_str(&format!("    _count: t.field({{ type: {} }}),\n", nested("Int", false, false)));
+        if let Some(average) = filter.average {
+            content.push_str(&format!("    _avg: t.field({{ type: {} }}),\n", nested(average, nullable, false)));
+            content.push_str(&format!("    _sum: t.field({{ type: {} }}),\n", nested(filter.name, nullable, false)));
+        }
+        content.push_str(&format!("    _min: t.field({{ type: {} }}),\n", nested(filter.name, nullable, false)));
+        content.push_str(&format!("    _max: t.field({{ type: {} }}),\n", nested(filter.name, nullable, false)));
+    }
+
+    content.push_str("  }),\n});\n");
+}
@@ -1,3 +1,4 @@
+use crate::parser::{Field, FieldType, Model, ParsedSchema};
 use anyhow::Result;
 use std::fs;
 use std::path::Path;
@@ -16,19 +17,35 @@ pub fn get_prisma_name(model: &str) -> PrismaNames {
     let camel_case = to_camel_case(&lower_first);
 
     PrismaNames {
-        model: model.to_string(),
         update: format!("updateOne{}", model),
+        update_many: format!("updateMany{}", capitalize_first(model)),
+        upsert: format!("upsertOne{}", model),
         create: format!("createOne{}", model),
         create_many: format!("createMany{}", capitalize_first(model)),
+        delete: format!("deleteOne{}", model),
+        delete_many: format!("deleteMany{}", capitalize_first(model)),
         find: camel_case.clone(),  // This corresponds to minusStar with camelCase transformation
         find_many: pluralize_query_name(&camel_case), // This is based on minusStar (camelCase) with pluralization
+        find_first: format!("findFirst{}", model),
+        find_first_or_throw: format!("findFirst{}OrThrow", model),
+        find_unique_or_throw: format!("get{}", model), // TypeGraphQL-prisma exposes findUniqueOrThrow as get{Model}
+        group_by: format!("groupBy{}", capitalize_first(model)),
         where_input: format!("{}WhereInput", model),
         where_unique_input: format!("{}WhereUniqueInput", model),
+        scalar_where_with_aggregates_input: format!("{}ScalarWhereWithAggregatesInput", model),
         order_by_input: format!("{}OrderByInput", model),
+        order_by_with_aggregation_input: format!("{}OrderByWithAggregationInput", model),
+        scalar_field_enum: format!("{}ScalarFieldEnum", model),
+        count_aggregate: format!("{}CountAggregate", model),
+        sum_aggregate: format!("{}SumAggregate", model),
+        avg_aggregate: format!("{}AvgAggregate", model),
+        min_aggregate: format!("{}MinAggregate", model),
+        max_aggregate: format!("{}MaxAggregate", model),
+        group_by_output: format!("{}GroupBy", model),
         create_input: format!("{}CreateInput", model),
         create_many_input: format!("{}CreateManyInput", capitalize_first(model)),
         update_input: format!("{}UpdateInput", model),
-        query_new: pluralize_find_many_name_original(&lower_first),  // Use lower_first version (without camelCase) for query_new
+        update_many_input: format!("{}UpdateManyMutationInput", capitalize_first(model)),
         query_new2: lower_first.clone(), // querynew2 is (model.charAt(0).toLowerCase() + model.slice(1)) WITHOUT camelCase transformation
     }
 }
@@ -42,6 +59,11 @@ pub fn capitalize_first(s: &str) -> String {
     }
 }
 
+/// Quote a string as a JavaScript string literal, e.g. for `description:`
+pub fn js_string(s: &str) -> String {
+    serde_json::Value::from(s).to_string()
+}
+
 /// Convert snake_case to camelCase
 fn to_camel_case(input: &str) -> String {
     let parts: Vec<&str> = input.split('_').collect();
@@ -67,66 +89,113 @@ fn to_camel_case(input: &str) -> String {
 /// Pluralize query name according to the JavaScript function (findMany)
 /// This implements: minusStar.replace(/y$/g,'ie') + 's'
 fn pluralize_query_name(s: &str) -> String {
-    if s.ends_with('y') {
-        format!("{}ie{}", &s[..s.len() - 1], 's')  // Replace y with 'ie' then add 's' as per JS
-    } else {
-        format!("{}s", s)
-    }
-}
-
-/// Pluralize find many name according to the JavaScript function (querynew)
-/// This implements: (model.toLowerCase...).replace(/y$/g,'ie') + 's', then replace(/ss$/g, 's')
-fn pluralize_find_many_name_original(s: &str) -> String {
-    let base = if s.ends_with('y') {
-        format!("{}ie{}", &s[..s.len() - 1], 's')  // Replace y with 'ie' then add 's' as per JS
-    } else {
-        format!("{}s", s)
-    };
-
-    // Replace ss with s at the end as per JavaScript function
-    if base.ends_with("ss") {
-        format!("{}s", &base[..base.len() - 1])  // Remove one 's' to get "s" instead of "ss"
-    } else {
-        base
-    }
-}
-
-/// Pluralize find many name according to the JavaScript function (findMany)
-/// This implements: minusStar.replace(/y$/g,'ie') + 's'
-fn pluralize_find_many_name(s: &str) -> String {
-    if s.ends_with('y') {
-        format!("{}ie{}", &s[..s.len() - 1], 's')  // Replace y with 'ie' then add 's' as per JS for findMany
-    } else {
-        format!("{}s", s)
+    match s.strip_suffix('y') {
+        Some(stem) => format!("{}ie{}", stem, 's'),  // Replace y with 'ie' then add 's' as per JS
+        None => format!("{}s", s),
     }
 }
 
 /// Struct to hold all the generated names for a model
 #[derive(Debug)]
 pub struct PrismaNames {
-    pub model: String,
     pub update: String,
+    pub update_many: String,
+    pub upsert: String,
     pub create: String,
     pub create_many: String,
+    pub delete: String,
+    pub delete_many: String,
     pub find: String,
     pub find_many: String,
+    pub find_first: String,
+    pub find_first_or_throw: String,
+    pub find_unique_or_throw: String,
+    pub group_by: String,
     pub where_input: String,
     pub where_unique_input: String,
+    pub scalar_where_with_aggregates_input: String,
     pub order_by_input: String,
+    pub order_by_with_aggregation_input: String,
+    pub scalar_field_enum: String,
+    pub count_aggregate: String,
+    pub sum_aggregate: String,
+    pub avg_aggregate: String,
+    pub min_aggregate: String,
+    pub max_aggregate: String,
+    pub group_by_output: String,
     pub create_input: String,
     pub create_many_input: String,
     pub update_input: String,
-    pub query_new: String,
+    pub update_many_input: String,
     pub query_new2: String,
 }
 
+/// Relay cursor of a model: the `@id` field or the compound `@@id` (e.g. `userId_roleId`), or else
+/// the first `@unique` field or compound `@@unique`; cursors only use fields of the object type
+pub fn relay_cursor(model: &Model) -> Option<String> {
+    let visible = |name: &str| {
+        model
+            .fields
+            .iter()
+            .any(|f| f.name == name && f.relation.is_none() && !f.is_list && !f.directives.omit_output)
+    };
+    let field = |unique: fn(&Field) -> bool| {
+        model
+            .fields
+            .iter()
+            .find(|f| unique(f) && visible(&f.name))
+            .map(|f| f.name.clone())
+    };
+    // Pothos takes compound keys by the name Prisma gives them in `where`
+    let compound = |fields: &[String], name: &Option<String>| {
+        fields
+            .iter()
+            .all(|f| visible(f))
+            .then(|| name.clone().unwrap_or_else(|| fields.join("_")))
+    };
+
+    field(|f| f.is_id)
+        .or_else(|| model.primary_key.as_ref().and_then(|pk| compound(&pk.fields, &pk.name)))
+        .or_else(|| field(|f| f.is_unique))
+        .or_else(|| model.unique_fields.iter().find_map(|u| compound(&u.fields, &u.name)))
+}
+
+/// Filters and write inputs carry Json null sentinels as soon as the schema has a Json field
+pub fn has_json_nulls(schema: &ParsedSchema) -> bool {
+    schema
+        .models
+        .iter()
+        .flat_map(|m| &m.fields)
+        .any(|f| matches!(f.field_type, FieldType::Json) && !f.is_list)
+}
+
+/// `args.{name}`, passed through `withJsonNulls` when the inputs have Json null sentinels
+pub fn prisma_arg(name: &str, json_nulls: bool) -> String {
+    if json_nulls {
+        format!("withJsonNulls(args.{})", name)
+    } else {
+        format!("args.{}", name)
+    }
+}
+
+/// Builder import of `withJsonNulls`, for `prisma_arg`
+pub fn json_nulls_import(json_nulls: bool) -> &'static str {
+    if json_nulls {
+        ", withJsonNulls"
+    } else {
+        ""
+    }
+}
+
 /// Generate builder file for Pothos
-pub fn generate_helpers(output_dir: &Path) -> Result<()> {
-    let content = r#"import SchemaBuilder from "@pothos/core";
+/// With `relay`, the builder also registers `@pothos/plugin-relay` for connections
+pub fn generate_helpers(output_dir: &Path, relay: bool) -> Result<()> {
+    let template = r#"import SchemaBuilder from "@pothos/core";
 import PrismaPlugin from "@pothos/plugin-prisma";
 import { Prisma, PrismaClient } from "@prisma/client";
 import SimpleObjectsPlugin from '@pothos/plugin-simple-objects';
-import type PrismaTypes from "@pothos/plugin-prisma/generated";
+{relay_import}import type PrismaTypes from "@pothos/plugin-prisma/generated";
+import { GraphQLError, GraphQLResolveInfo, Kind, SelectionSetNode, FieldNode } from "graphql";
 
 // Initialize Prisma Client
 export const prisma = new PrismaClient();
@@ -150,11 +219,23 @@ export const builder = new SchemaBuilder<{
       Input: unknown;
       Output: unknown;
     };
+    Decimal: {
+      Input: Prisma.Decimal;
+      Output: Prisma.Decimal;
+    };
+    BigInt: {
+      Input: bigint;
+      Output: bigint;
+    };
+    Bytes: {
+      Input: Buffer;
+      Output: Uint8Array;
+    };
   };
   // @ts-ignore
 }>({
-  plugins: [PrismaPlugin, SimpleObjectsPlugin],
-  prisma: {
+  plugins: [{relay_plugin}PrismaPlugin, SimpleObjectsPlugin],
+{relay_options}  prisma: {
     client: prisma,
     dmmf: Prisma.dmmf,
     exposeDescriptions: true,
@@ -174,19 +255,117 @@ builder.scalarType("JSON", {
   parseValue: (value) => value,
 });
 
-// AffectedRowsOutput type for createMany operations
+// Prisma's null sentinels for Json fields, the values of the NullableJsonNullValueInput
+// and JsonNullValueFilter enums
+const jsonNulls: unknown[] = [Prisma.DbNull, Prisma.JsonNull, Prisma.AnyNull];
+
+// Move the sentinel of every `{field}Null` input to `{field}`, where Prisma expects it
+export function withJsonNulls<T>(value: T): T {
+  if (Array.isArray(value)) {
+    return value.map(withJsonNulls) as T;
+  }
+  // Leave scalars, sentinels and class instances (Date, Decimal, Buffer...) as they are
+  if (value === null || typeof value !== "object") {
+    return value;
+  }
+  const prototype = Object.getPrototypeOf(value);
+  if (prototype !== Object.prototype && prototype !== null) {
+    return value;
+  }
+
+  const result: Record<string, unknown> = {};
+  for (const [key, field] of Object.entries(value)) {
+    if (key.endsWith("Null") && jsonNulls.includes(field)) {
+      result[key.slice(0, -"Null".length)] = field;
+    } else {
+      result[key] = withJsonNulls(field);
+    }
+  }
+  return result as T;
+}
+
+// Add Decimal scalar, sent as a string so money values keep their precision
+builder.scalarType("Decimal", {
+  serialize: (value) => value.toString(),
+  parseValue: (value) => new Prisma.Decimal(value as string | number),
+});
+
+// Add BigInt scalar, sent as a string because JSON numbers cannot hold it
+builder.scalarType("BigInt", {
+  serialize: (value) => value.toString(),
+  parseValue: (value) => BigInt(value as string | number),
+});
+
+// Add Bytes scalar, sent as base64
+builder.scalarType("Bytes", {
+  serialize: (value) => Buffer.from(value).toString("base64"),
+  parseValue: (value) => Buffer.from(value as string, "base64"),
+});
+
+// AffectedRowsOutput type for createMany, updateMany and deleteMany operations
 export const AffectedRowsOutput = builder.simpleObject("AffectedRowsOutput", {
   fields: (t) => ({
     count: t.int({ nullable: false }),
   }),
 });
 
+// Error thrown by the *OrThrow queries when no record matches
+export class NotFoundError extends GraphQLError {
+  constructor(model: string) {
+    super(`No ${model} found`, { extensions: { code: "NOT_FOUND", model } });
+  }
+}
+
+// Turn Prisma's "record not found" error (P2025) into a NotFoundError
+export const rethrowNotFound = (model: string) => (error: unknown): never => {
+  if (error instanceof Prisma.PrismaClientKnownRequestError && error.code === "P2025") {
+    throw new NotFoundError(model);
+  }
+  throw error;
+};
+
+// Fields of a selection set, with fragments expanded
+const selectedFields = (selectionSet: SelectionSetNode | undefined, info: GraphQLResolveInfo): FieldNode[] =>
+  (selectionSet?.selections ?? []).flatMap((selection) => {
+    if (selection.kind === Kind.FIELD) return [selection];
+    if (selection.kind === Kind.INLINE_FRAGMENT) return selectedFields(selection.selectionSet, info);
+    return selectedFields(info.fragments[selection.name.value]?.selectionSet, info);
+  });
+
+const AGGREGATES = new Set(["_count", "_sum", "_avg", "_min", "_max"]);
+
+// Prisma aggregations (`{ _sum: { views: true } }`) for the aggregates selected in the query
+export const aggregateSelection = (info: GraphQLResolveInfo) => {
+  const selection: Record<string, Record<string, true>> = {};
+  const aggregates = info.fieldNodes.flatMap((node) => selectedFields(node.selectionSet, info));
+  for (const aggregate of aggregates) {
+    if (!AGGREGATES.has(aggregate.name.value)) continue;
+    const fields = (selection[aggregate.name.value] ??= {});
+    for (const field of selectedFields(aggregate.selectionSet, info)) {
+      if (field.name.value !== "__typename") fields[field.name.value] = true;
+    }
+  }
+  return selection;
+};
+
 // Initialize Query and Mutation types
 builder.queryType({});
 builder.mutationType({});
 builder.subscriptionType({});
 "#;
 
+    let content = if relay {
+        template
+            .replace("{relay_import}", "import RelayPlugin from \"@pothos/plugin-relay\";\n")
+            .replace("{relay_plugin}", "RelayPlugin, ")
+            .replace("{relay_options}", "  relay: {\n    clientMutationId: \"omit\",\n    cursorType: \"String\",\n  },\n")
+    } else {
+        template
+            .replace("{relay_import}", "")
+            .replace("{relay_plugin}", "")
+            .replace("{relay_options}", "")
+    };
+
     fs::write(output_dir.join("builder.ts"), content)?;
     Ok(())
 }
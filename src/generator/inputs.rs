@@ -1,6 +1,10 @@
-use crate::parser::{FieldType, Model};
+use crate::parser::{Field, FieldType, Model, ParsedSchema, Provider};
+use crate::generator::filters::{filter_name, update_operations_name};
+use crate::generator::aggregates::{is_groupable, min_max_field, sum_field};
 use crate::generator::get_prisma_name;
-use anyhow::Result;
+use crate::generator::helpers::{capitalize_first, js_string};
+use crate::generator::relations::{create_relation_input_name, update_relation_input_name};
+use anyhow::{bail, Result};
 use std::collections::HashSet;
 use std::fs;
 use std::path::Path;
@@ -15,25 +19,35 @@ fn collect_enum_types(model: &Model) -> HashSet<String> {
         if let FieldType::Enum(enum_name) = &field.field_type {
             used_enums.insert(enum_name.clone());
         }
+        if json_null_field(field).is_some() {
+            used_enums.insert("NullableJsonNullValueInput".to_string());
+        }
     }
     used_enums
 }
 
 /// Generate all Pothos input types for a model
-pub fn generate_inputs(model: &Model, output_dir: &Path) -> Result<()> {
+/// With `update_operations`, scalar fields of update inputs use `{Type}FieldUpdateOperationsInput`
+pub fn generate_inputs(model: &Model, schema: &ParsedSchema, output_dir: &Path, update_operations: bool) -> Result<()> {
     let inputs_dir = output_dir.join("inputs");
     fs::create_dir_all(&inputs_dir)?;
 
     generate_create_input(model, &inputs_dir)?;
     generate_create_many_input(model, &inputs_dir)?;
-    generate_update_input(model, &inputs_dir)?;
+    generate_update_input(model, &inputs_dir, update_operations)?;
+    generate_update_many_input(model, &inputs_dir, update_operations)?;
+    generate_list_update_inputs(model, &inputs_dir)?;
     generate_where_input(model, &inputs_dir)?;
+    generate_scalar_where_with_aggregates_input(model, &inputs_dir)?;
+    generate_compound_unique_inputs(model, &inputs_dir)?;
     generate_where_unique_input(model, &inputs_dir)?;
-    generate_order_by_input(model, &inputs_dir)?;
+    let nulls_order = schema.supports(Provider::supports_nulls_order);
+    generate_order_by_input(model, &inputs_dir, nulls_order)?;
+    generate_order_by_with_aggregation_input(model, &inputs_dir, nulls_order)?;
+    generate_order_by_relation_aggregate_input(model, &inputs_dir)?;
     
     // Generate relation-specific input types
     generate_where_unique_input_for_relations(model, &inputs_dir)?;
-    generate_relation_create_input(model, &inputs_dir)?;
     generate_relation_filter(model, &inputs_dir)?;
     generate_list_relation_filter(model, &inputs_dir)?;
 
@@ -54,16 +68,10 @@ fn generate_create_input(model: &Model, dir: &Path) -> Result<()> {
     // Import relation input types for nested relations
     let mut relation_imports: Vec<String> = Vec::new();
     for field in &model.fields {
-        if let Some(_relation) = &field.relation {
-            if let FieldType::Model(related_model) = &field.field_type {
-                let relation_input_name = if field.is_list {
-                    format!("{}{}ListRelationInput", model.name, related_model)
-                } else {
-                    format!("{}{}RelationInput", model.name, related_model)
-                };
-                if !relation_imports.contains(&relation_input_name) {
-                    relation_imports.push(relation_input_name);
-                }
+        if field.relation.is_some() {
+            let relation_input_name = create_relation_input_name(model, field);
+            if !relation_imports.contains(&relation_input_name) {
+                relation_imports.push(relation_input_name);
             }
         }
     }
@@ -82,59 +90,87 @@ fn generate_create_input(model: &Model, dir: &Path) -> Result<()> {
     ));
     content.push_str("  fields: (t) => ({\n");
 
-    // Collect all foreign keys to skip them
-    let mut foreign_keys: Vec<String> = Vec::new();
-    for field in &model.fields {
-        if let Some(relation) = &field.relation {
-            if !relation.fields.is_empty() {
-                foreign_keys.extend(relation.fields.clone());
-            }
-        }
+    for field in create_input_fields(model, None) {
+        content.push_str(&format!("    {},\n", field));
     }
 
+    content.push_str("  }),\n");
+    content.push_str("});\n");
+
+    fs::write(dir.join(format!("{}.ts", input_name)), content)?;
+
+    Ok(())
+}
+
+/// Foreign key scalars of the model's relations
+fn foreign_keys(model: &Model) -> Vec<&String> {
+    model
+        .fields
+        .iter()
+        .filter_map(|f| f.relation.as_ref())
+        .flat_map(|r| &r.fields)
+        .collect()
+}
+
+/// Fields of a create input; nested writes pass `without`, the relation back to the parent,
+/// which Prisma fills in itself
+pub fn create_input_fields(model: &Model, without: Option<&Field>) -> Vec<String> {
+    let foreign_keys = foreign_keys(model);
+    let mut fields = Vec::new();
+
     for field in &model.fields {
-        // Skip only foreign keys (for backward compatibility, include all timestamp fields)
-        if foreign_keys.contains(&field.name) {
+        // Skip foreign keys and fields hidden with `@pothos.omit(create)` or `@pothos.readonly`
+        if foreign_keys.contains(&&field.name)
+            || field.directives.omit_create
+            || without.is_some_and(|w| w.name == field.name)
+        {
             continue;
         }
 
-        // Handle relation fields
-        if let Some(_relation) = &field.relation {
-            // Include ALL relation fields for nested creates
-            // Both owning side (with foreign keys) and reverse side (without foreign keys)
-            // Prisma supports nested creates for both
-            
-            if let FieldType::Model(related_model) = &field.field_type {
-                let relation_input_type = if field.is_list {
-                    format!("{}{}ListRelationInput", model.name, related_model)
-                } else {
-                    format!("{}{}RelationInput", model.name, related_model)
-                };
-                let required_option = if field.is_required { ", required: true" } else { "" };
-                content.push_str(&format!(
-                    "    {}: t.field({{ type: {}{} }}),\n",
-                    field.name, relation_input_type, required_option
-                ));
-            }
+        // Both the owning side (with foreign keys) and the reverse side of a relation accept nested creates
+        if field.relation.is_some() {
+            let relation_input_type = create_relation_input_name(model, field);
+            let options = input_options(field, field.is_required);
+            fields.push(typed_input_field(&field.name, &relation_input_type, &options));
             continue;
         }
 
-        let required = if field.is_id || !field.is_required || field.default_value.is_some() {
-            ""
-        } else {
-            "required: true"
-        };
-
-        let field_code = generate_input_field(&field.field_type, &field.name, field.is_list, required);
-        content.push_str(&format!("    {},\n", field_code));
+        let required = create_required(field);
+        let options = input_options(field, required);
+        fields.push(generate_input_field(&field.field_type, &field.name, field.is_list, &options));
+        fields.extend(json_null_field(field));
     }
 
-    content.push_str("  }),\n");
-    content.push_str("});\n");
+    fields
+}
 
-    fs::write(dir.join(format!("{}.ts", input_name)), content)?;
+/// Fields of an update input, leaving out `without` like `create_input_fields`
+pub fn update_input_fields(model: &Model, without: Option<&Field>, operations: bool) -> Vec<String> {
+    let foreign_keys = foreign_keys(model);
+    let mut fields = Vec::new();
 
-    Ok(())
+    for field in &model.fields {
+        // Skip foreign keys and fields hidden with `@pothos.omit(update)` or `@pothos.readonly`
+        if foreign_keys.contains(&&field.name)
+            || field.directives.omit_update
+            || without.is_some_and(|w| w.name == field.name)
+        {
+            continue;
+        }
+
+        // Both sides of a relation can be written, like in create inputs
+        if field.relation.is_some() {
+            let relation_input_type = update_relation_input_name(model, field);
+            let options = input_options(field, false);
+            fields.push(typed_input_field(&field.name, &relation_input_type, &options));
+            continue;
+        }
+
+        fields.push(update_field(model, field, operations));
+        fields.extend(json_null_field(field));
+    }
+
+    fields
 }
 
 /// Generate CreateManyInput - only scalar fields and foreign keys, NO nested relations
@@ -160,21 +196,21 @@ fn generate_create_many_input(model: &Model, dir: &Path) -> Result<()> {
 
     for field in &model.fields {
         // Skip relation fields entirely - createMany doesn't support nested creates
-        if field.relation.is_some() {
+        if field.relation.is_some() || field.directives.omit_create {
             continue;
         }
 
         // Include foreign keys for createMany (unlike createOne which skips them)
         // The foreign key fields are scalar fields that reference other tables
         
-        let required = if field.is_id || !field.is_required || field.default_value.is_some() {
-            ""
-        } else {
-            "required: true"
-        };
+        let required = create_required(field);
+        let options = input_options(field, required);
 
-        let field_code = generate_input_field(&field.field_type, &field.name, field.is_list, required);
+        let field_code = generate_input_field(&field.field_type, &field.name, field.is_list, &options);
         content.push_str(&format!("    {},\n", field_code));
+        if let Some(null_field) = json_null_field(field) {
+            content.push_str(&format!("    {},\n", null_field));
+        }
     }
 
     content.push_str("  }),\n");
@@ -185,7 +221,7 @@ fn generate_create_many_input(model: &Model, dir: &Path) -> Result<()> {
     Ok(())
 }
 
-fn generate_update_input(model: &Model, dir: &Path) -> Result<()> {
+fn generate_update_input(model: &Model, dir: &Path, operations: bool) -> Result<()> {
     let names = get_prisma_name(&model.name);
     let mut content = String::new();
     let used_enums = collect_enum_types(model);
@@ -199,16 +235,10 @@ fn generate_update_input(model: &Model, dir: &Path) -> Result<()> {
     // Import relation input types for nested relations
     let mut relation_imports: Vec<String> = Vec::new();
     for field in &model.fields {
-        if let Some(_relation) = &field.relation {
-            if let FieldType::Model(related_model) = &field.field_type {
-                let relation_input_name = if field.is_list {
-                    format!("{}{}ListRelationInput", model.name, related_model)
-                } else {
-                    format!("{}{}RelationInput", model.name, related_model)
-                };
-                if !relation_imports.contains(&relation_input_name) {
-                    relation_imports.push(relation_input_name);
-                }
+        if field.relation.is_some() {
+            let relation_input_name = update_relation_input_name(model, field);
+            if !relation_imports.contains(&relation_input_name) {
+                relation_imports.push(relation_input_name);
             }
         }
     }
@@ -216,6 +246,10 @@ fn generate_update_input(model: &Model, dir: &Path) -> Result<()> {
     if !relation_imports.is_empty() {
         content.push_str(&format!("import {{ {} }} from \"./relations\";\n", relation_imports.join(", ")));
     }
+    push_list_update_imports(&mut content, model);
+    if operations {
+        push_update_operations_imports(&mut content, model);
+    }
     
     content.push('\n');
 
@@ -227,47 +261,55 @@ fn generate_update_input(model: &Model, dir: &Path) -> Result<()> {
     ));
     content.push_str("  fields: (t) => ({\n");
 
-    // Collect all foreign keys to skip them
-    let mut foreign_keys: Vec<String> = Vec::new();
-    for field in &model.fields {
-        if let Some(relation) = &field.relation {
-            if !relation.fields.is_empty() {
-                foreign_keys.extend(relation.fields.clone());
-            }
-        }
+    for field in update_input_fields(model, None, operations) {
+        content.push_str(&format!("    {},\n", field));
     }
 
+    content.push_str("  }),\n");
+    content.push_str("});\n");
+
+    fs::write(dir.join(format!("{}.ts", input_name)), content)?;
+
+    Ok(())
+}
+
+/// Generate UpdateManyMutationInput - only scalar fields, NO relations or foreign keys
+fn generate_update_many_input(model: &Model, dir: &Path, operations: bool) -> Result<()> {
+    let names = get_prisma_name(&model.name);
+    let mut content = String::new();
+    let used_enums = collect_enum_types(model);
+
+    content.push_str("import { builder } from \"../builder\";\n");
+    if !used_enums.is_empty() {
+        let enum_imports: Vec<String> = used_enums.into_iter().collect();
+        content.push_str(&format!("import {{ {} }} from \"../enums\";\n", enum_imports.join(", ")));
+    }
+    push_list_update_imports(&mut content, model);
+    if operations {
+        push_update_operations_imports(&mut content, model);
+    }
+    content.push('\n');
+
+    let input_name = names.update_many_input;
+
+    content.push_str(&format!(
+        "export const {} = builder.inputType(\"{}\", {{\n",
+        input_name, input_name
+    ));
+    content.push_str("  fields: (t) => ({\n");
+
+    let foreign_keys = foreign_keys(model);
+
     for field in &model.fields {
-        // Skip only foreign keys (for backward compatibility, include id and timestamps)
-        if foreign_keys.contains(&field.name) {
+        // updateMany cannot touch relations, and Prisma only accepts foreign keys in its unchecked variant
+        if field.relation.is_some() || foreign_keys.contains(&&field.name) || field.directives.omit_update {
             continue;
         }
 
-        // Handle relation fields
-        if let Some(relation) = &field.relation {
-            // Include only relations WITH fields (the "owning" side with foreign key)
-            // These are the ones where we can connect/create related records
-            // Skip reverse relations (without fields) to avoid duplication
-            if relation.fields.is_empty() {
-                continue;
-            }
-            
-            if let FieldType::Model(related_model) = &field.field_type {
-                let relation_input_type = if field.is_list {
-                    format!("{}{}ListRelationInput", model.name, related_model)
-                } else {
-                    format!("{}{}RelationInput", model.name, related_model)
-                };
-                content.push_str(&format!(
-                    "    {}: t.field({{ type: {} }}),\n",
-                    field.name, relation_input_type
-                ));
-            }
-            continue;
+        content.push_str(&format!("    {},\n", update_field(model, field, operations)));
+        if let Some(null_field) = json_null_field(field) {
+            content.push_str(&format!("    {},\n", null_field));
         }
-
-        let field_code = generate_input_field(&field.field_type, &field.name, field.is_list, "");
-        content.push_str(&format!("    {},\n", field_code));
     }
 
     content.push_str("  }),\n");
@@ -278,12 +320,110 @@ fn generate_update_input(model: &Model, dir: &Path) -> Result<()> {
     Ok(())
 }
 
+/// Name of the `set`/`push` wrapper of a scalar list field, with the field name as written
+/// like Prisma's, e.g. `PostUpdatetagsInput`
+pub fn list_update_input_name(model: &Model, field: &Field) -> String {
+    format!("{}Update{}Input", model.name, field.name)
+}
+
+/// Scalar list fields that can be written in update and updateMany
+pub fn list_update_fields(model: &Model) -> Vec<&Field> {
+    model
+        .fields
+        .iter()
+        .filter(|f| f.relation.is_none() && f.is_list && !f.directives.omit_update)
+        .collect()
+}
+
+/// Generate `{Model}Update{field}Input` with `set` and `push` for every scalar list field
+/// Scalar lists only exist on providers that support them, so nothing is generated elsewhere
+fn generate_list_update_inputs(model: &Model, dir: &Path) -> Result<()> {
+    for field in list_update_fields(model) {
+        let input_name = list_update_input_name(model, field);
+        let mut content = String::from("import { builder } from \"../builder\";\n");
+        if let FieldType::Enum(enum_name) = &field.field_type {
+            content.push_str(&format!("import {{ {} }} from \"../enums\";\n", enum_name));
+        }
+        content.push('\n');
+
+        content.push_str(&format!(
+            "export const {} = builder.inputType(\"{}\", {{\n",
+            input_name, input_name
+        ));
+        content.push_str("  fields: (t) => ({\n");
+        content.push_str(&format!("    {},\n", generate_input_field(&field.field_type, "set", true, "")));
+        content.push_str(&format!("    {},\n", generate_input_field(&field.field_type, "push", true, "")));
+        content.push_str("  }),\n");
+        content.push_str("});\n");
+
+        fs::write(dir.join(format!("{}.ts", input_name)), content)?;
+    }
+
+    Ok(())
+}
+
+/// `import { ... } from "./{Model}Update{field}Input"` for every list wrapper of the model
+pub fn push_list_update_imports(content: &mut String, model: &Model) {
+    for field in list_update_fields(model) {
+        let input_name = list_update_input_name(model, field);
+        content.push_str(&format!("import {{ {} }} from \"./{}\";\n", input_name, input_name));
+    }
+}
+
+/// `{Type}FieldUpdateOperationsInput` types used by the scalar fields of the model's update inputs
+pub fn update_operations_imports(model: &Model) -> Vec<String> {
+    let foreign_keys = foreign_keys(model);
+    let mut imports: Vec<String> = model
+        .fields
+        .iter()
+        .filter(|f| f.relation.is_none() && !foreign_keys.contains(&&f.name) && !f.directives.omit_update)
+        .filter_map(update_operations_type)
+        .collect();
+    imports.sort();
+    imports.dedup();
+    imports
+}
+
+/// `import { ... } from "./updateOperations"` for the scalar fields of the model's update inputs
+fn push_update_operations_imports(content: &mut String, model: &Model) {
+    let imports = update_operations_imports(model);
+    if !imports.is_empty() {
+        content.push_str(&format!("import {{ {} }} from \"./updateOperations\";\n", imports.join(", ")));
+    }
+}
+
+/// `{Type}FieldUpdateOperationsInput` of a scalar field; Json and lists have none
+fn update_operations_type(field: &Field) -> Option<String> {
+    if field.is_list || matches!(field.field_type, FieldType::Json) {
+        return None;
+    }
+    Some(update_operations_name(&filter_base(&field.field_type), !field.is_required))
+}
+
+/// Scalar field of an update input; lists go through their `set`/`push` wrapper,
+/// and the other fields through their update operations input when `operations` is on
+fn update_field(model: &Model, field: &Field, operations: bool) -> String {
+    let options = input_options(field, false);
+    if field.is_list {
+        return typed_input_field(&field.name, &list_update_input_name(model, field), &options);
+    }
+    match update_operations_type(field) {
+        Some(type_name) if operations => typed_input_field(&field.name, &type_name, &options),
+        _ => generate_input_field(&field.field_type, &field.name, false, &options),
+    }
+}
+
 fn generate_where_input(model: &Model, dir: &Path) -> Result<()> {
     let names = get_prisma_name(&model.name);
     let mut content = String::new();
 
     content.push_str("import { builder } from \"../builder\";\n");
-    content.push_str("import { StringFilter, IntFilter, FloatFilter, BoolFilter, DateTimeFilter } from \"./filters\";\n");
+    let scalar_fields: Vec<&Field> = model
+        .fields
+        .iter()
+        .filter(|f| f.relation.is_none() && !f.directives.omit_where)
+        .collect();
+    content.push_str(&filter_imports(&scalar_fields, false));
     
     // Import RelationFilter and ListRelationFilter types for related models
     let mut related_filters: Vec<String> = Vec::new();
@@ -333,21 +473,15 @@ fn generate_where_input(model: &Model, dir: &Path) -> Result<()> {
     ));
 
     // Scalar fields with filters
-    for field in &model.fields {
-        if field.relation.is_some() {
-            continue;
-        }
-
-        let filter_type = get_filter_type(&field.field_type);
-        content.push_str(&format!(
-            "    {}: t.field({{ type: {} }}),\n",
-            field.name, filter_type
-        ));
+    for field in &scalar_fields {
+        let filter_type = get_filter_type(field, false);
+        let options = input_options(field, false);
+        content.push_str(&format!("    {},\n", typed_input_field(&field.name, &filter_type, &options)));
     }
 
     // Relation fields with nested WhereInput
     for field in &model.fields {
-        if field.relation.is_none() {
+        if field.relation.is_none() || field.directives.omit_where {
             continue;
         }
 
@@ -356,17 +490,13 @@ fn generate_where_input(model: &Model, dir: &Path) -> Result<()> {
             if field.is_list {
                 // List relations use ListRelationFilter with some/every/none
                 let list_relation_filter_name = format!("{}ListRelationFilter", related_model);
-                content.push_str(&format!(
-                    "    {}: t.field({{ type: {} }}),\n",
-                    field.name, list_relation_filter_name
-                ));
+                let options = input_options(field, false);
+                content.push_str(&format!("    {},\n", typed_input_field(&field.name, &list_relation_filter_name, &options)));
             } else {
                 // Single relations use a RelationFilter with is/isNot
                 let relation_filter_name = format!("{}RelationFilter", related_model);
-                content.push_str(&format!(
-                    "    {}: t.field({{ type: {} }}),\n",
-                    field.name, relation_filter_name
-                ));
+                let options = input_options(field, false);
+                content.push_str(&format!("    {},\n", typed_input_field(&field.name, &relation_filter_name, &options)));
             }
         }
     }
@@ -379,13 +509,21 @@ fn generate_where_input(model: &Model, dir: &Path) -> Result<()> {
     Ok(())
 }
 
-fn generate_where_unique_input(model: &Model, dir: &Path) -> Result<()> {
+/// Generate the `having` input of groupBy: scalar fields only, filtering on values and aggregates
+fn generate_scalar_where_with_aggregates_input(model: &Model, dir: &Path) -> Result<()> {
     let names = get_prisma_name(&model.name);
     let mut content = String::new();
 
-    content.push_str("import { builder } from \"../builder\";\n\n");
+    content.push_str("import { builder } from \"../builder\";\n");
+    let scalar_fields: Vec<&Field> = model
+        .fields
+        .iter()
+        .filter(|f| f.relation.is_none() && !f.is_list && is_groupable(f))
+        .collect();
+    content.push_str(&filter_imports(&scalar_fields, true));
+    content.push('\n');
 
-    let input_name = names.where_unique_input;
+    let input_name = names.scalar_where_with_aggregates_input;
 
     content.push_str(&format!(
         "export const {} = builder.inputType(\"{}\", {{\n",
@@ -393,15 +531,175 @@ fn generate_where_unique_input(model: &Model, dir: &Path) -> Result<()> {
     ));
     content.push_str("  fields: (t) => ({\n");
 
-    // ID and unique fields (all optional - user can specify any one)
-    for field in &model.fields {
-        if field.is_id || field.is_unique {
-            if field.relation.is_some() {
-                continue; // Skip relation fields in WhereUnique
-            }
-            let field_code = generate_input_field(&field.field_type, &field.name, false, "");
+    // AND, OR, NOT
+    for operator in ["AND", "OR", "NOT"] {
+        content.push_str(&format!(
+            "    {}: t.field({{ type: ['{}'] as any }}),\n",
+            operator, input_name
+        ));
+    }
+
+    for field in &scalar_fields {
+        let filter_type = get_filter_type(field, true);
+        let options = input_options(field, false);
+        content.push_str(&format!("    {},\n", typed_input_field(&field.name, &filter_type, &options)));
+    }
+
+    content.push_str("  }),\n");
+    content.push_str("});\n");
+
+    fs::write(dir.join(format!("{}.ts", input_name)), content)?;
+
+    Ok(())
+}
+
+fn generate_where_unique_input(model: &Model, dir: &Path) -> Result<()> {
+    let names = get_prisma_name(&model.name);
+    generate_unique_selector_input(model, &names.where_unique_input, dir)
+}
+
+/// A compound unique selector (`@@id([a, b])` or `@@unique([a, b])`), named the way Prisma Client names it
+struct CompoundUnique<'a> {
+    /// Key inside WhereUniqueInput, e.g. `userId_roleId` or the explicit `name:`
+    key: String,
+    /// Input type name, e.g. `UserRoleUserIdRoleIdCompoundUniqueInput`
+    input_name: String,
+    fields: Vec<&'a Field>,
+}
+
+/// Collect the compound unique selectors of a model: the composite primary key
+/// and every multi-field `@@unique`
+fn compound_uniques(model: &Model) -> Vec<CompoundUnique<'_>> {
+    let constraints = model
+        .primary_key
+        .iter()
+        .map(|pk| (&pk.fields, &pk.name))
+        .chain(model.unique_fields.iter().map(|u| (&u.fields, &u.name)));
+
+    let mut compounds: Vec<CompoundUnique> = Vec::new();
+    for (fields, name) in constraints {
+        if fields.len() < 2 {
+            continue;
+        }
+
+        let (key, suffix) = match name {
+            Some(name) => (name.clone(), capitalize_first(name)),
+            None => (
+                fields.join("_"),
+                fields.iter().map(|f| capitalize_first(f)).collect::<String>(),
+            ),
+        };
+
+        if compounds.iter().any(|c| c.key == key) {
+            continue;
+        }
+
+        let fields: Vec<&Field> = fields
+            .iter()
+            .filter_map(|name| model.fields.iter().find(|f| &f.name == name))
+            .collect();
+        // A selector is only usable if all of its fields can be filtered on
+        if fields.iter().any(|f| f.directives.omit_where) {
+            continue;
+        }
+
+        compounds.push(CompoundUnique {
+            key,
+            input_name: format!("{}{}CompoundUniqueInput", model.name, suffix),
+            fields,
+        });
+    }
+    compounds
+}
+
+/// Scalar fields that can select a single record on their own:
+/// `@id`, `@unique` and single-field `@@id`/`@@unique`, unless hidden from `where`
+fn single_unique_fields(model: &Model) -> Vec<&Field> {
+    let single_constraints: Vec<&String> = model
+        .primary_key
+        .iter()
+        .map(|pk| &pk.fields)
+        .chain(model.unique_fields.iter().map(|u| &u.fields))
+        .filter(|fields| fields.len() == 1)
+        .map(|fields| &fields[0])
+        .collect();
+
+    model
+        .fields
+        .iter()
+        .filter(|f| f.relation.is_none() && !f.directives.omit_where)
+        .filter(|f| f.is_id || f.is_unique || single_constraints.contains(&&f.name))
+        .collect()
+}
+
+/// Generate the nested input types used by compound unique selectors
+fn generate_compound_unique_inputs(model: &Model, dir: &Path) -> Result<()> {
+    for compound in compound_uniques(model) {
+        let mut content = String::new();
+
+        content.push_str("import { builder } from \"../builder\";\n");
+        let enum_imports = enum_imports(&compound.fields);
+        if !enum_imports.is_empty() {
+            content.push_str(&format!("import {{ {} }} from \"../enums\";\n", enum_imports.join(", ")));
+        }
+        content.push('\n');
+
+        content.push_str(&format!(
+            "export const {} = builder.inputType(\"{}\", {{\n",
+            compound.input_name, compound.input_name
+        ));
+        content.push_str("  fields: (t) => ({\n");
+
+        for field in &compound.fields {
+            let field_code = generate_input_field(&field.field_type, &field.name, false, &input_options(field, true));
             content.push_str(&format!("    {},\n", field_code));
         }
+
+        content.push_str("  }),\n");
+        content.push_str("});\n");
+
+        fs::write(dir.join(format!("{}.ts", compound.input_name)), content)?;
+    }
+
+    Ok(())
+}
+
+/// Generate a WhereUniqueInput-style type: every unique field plus the compound selectors
+fn generate_unique_selector_input(model: &Model, input_name: &str, dir: &Path) -> Result<()> {
+    let single_fields = single_unique_fields(model);
+    let compounds = compound_uniques(model);
+    let mut content = String::new();
+
+    content.push_str("import { builder } from \"../builder\";\n");
+    let enum_imports = enum_imports(&single_fields);
+    if !enum_imports.is_empty() {
+        content.push_str(&format!("import {{ {} }} from \"../enums\";\n", enum_imports.join(", ")));
+    }
+    for compound in &compounds {
+        content.push_str(&format!(
+            "import {{ {} }} from \"./{}\";\n",
+            compound.input_name, compound.input_name
+        ));
+    }
+    content.push('\n');
+
+    content.push_str(&format!(
+        "export const {} = builder.inputType(\"{}\", {{\n",
+        input_name, input_name
+    ));
+    content.push_str("  fields: (t) => ({\n");
+
+    // ID and unique fields (all optional - user can specify any one)
+    for field in &single_fields {
+        let field_code = generate_input_field(&field.field_type, &field.name, false, &input_options(field, false));
+        content.push_str(&format!("    {},\n", field_code));
+    }
+
+    for compound in &compounds {
+        content.push_str(&format!(
+            "    {}: t.field({{ type: {} }}),\n",
+            compound.key, compound.input_name
+        ));
     }
 
     content.push_str("  }),\n");
@@ -412,12 +710,54 @@ fn generate_where_unique_input(model: &Model, dir: &Path) -> Result<()> {
     Ok(())
 }
 
-fn generate_order_by_input(model: &Model, dir: &Path) -> Result<()> {
+/// Sorted enum names used by the given fields
+fn enum_imports(fields: &[&Field]) -> Vec<String> {
+    let mut enums: Vec<String> = fields
+        .iter()
+        .filter_map(|f| match &f.field_type {
+            FieldType::Enum(name) => Some(name.clone()),
+            _ => None,
+        })
+        .collect();
+    enums.sort();
+    enums.dedup();
+    enums
+}
+
+fn generate_order_by_input(model: &Model, dir: &Path, nulls_order: bool) -> Result<()> {
     let names = get_prisma_name(&model.name);
     let mut content = String::new();
 
+    // To-one relations sort by the fields of the related record, lists by their number of records
+    let mut relation_imports: Vec<String> = Vec::new();
+    let mut relation_fields: Vec<(&Field, String)> = Vec::new();
+    for field in &model.fields {
+        let (Some(_), FieldType::Model(related_model)) = (&field.relation, &field.field_type) else {
+            continue;
+        };
+        if field.directives.omit_order_by {
+            continue;
+        }
+        let type_name = if field.is_list {
+            format!("{}OrderByRelationAggregateInput", related_model)
+        } else {
+            get_prisma_name(related_model).order_by_input
+        };
+        if type_name != names.order_by_input && !relation_imports.contains(&type_name) {
+            relation_imports.push(type_name.clone());
+        }
+        relation_fields.push((field, type_name));
+    }
+
     content.push_str("import { builder } from \"../builder\";\n");
-    content.push_str("import { SortOrder } from \"../enums\";\n\n");
+    content.push_str("import { SortOrder } from \"../enums\";\n");
+    if nulls_order && has_nullable_sort_field(model) {
+        content.push_str("import { SortOrderInput } from \"./filters\";\n");
+    }
+    for import in &relation_imports {
+        content.push_str(&format!("import {{ {} }} from \"./{}\";\n", import, import));
+    }
+    content.push('\n');
 
     let input_name = names.order_by_input;
 
@@ -429,15 +769,130 @@ fn generate_order_by_input(model: &Model, dir: &Path) -> Result<()> {
 
     for field in &model.fields {
         if field.relation.is_some() {
+            if let Some((_, type_name)) = relation_fields.iter().find(|(f, _)| f.name == field.name) {
+                let options = input_options(field, false);
+                content.push_str(&format!("    {},\n", typed_input_field(&field.name, type_name, &options)));
+            }
+            continue;
+        }
+        if field.directives.omit_order_by {
             continue;
         }
+        content.push_str(&format!("    {},\n", sort_field(field, nulls_order)));
+    }
+
+    content.push_str("  }),\n");
+    content.push_str("});\n");
+
+    fs::write(dir.join(format!("{}.ts", input_name)), content)?;
+
+    Ok(())
+}
+
+/// Generate OrderByWithAggregationInput, the orderBy of groupBy: the model's columns, and the
+/// `_count`, `_avg`, `_sum`, `_min` and `_max` of each group
+fn generate_order_by_with_aggregation_input(model: &Model, dir: &Path, nulls_order: bool) -> Result<()> {
+    let names = get_prisma_name(&model.name);
+    let aggregates = aggregate_order_by_inputs(model);
+    // groupBy can only sort by fields it can group by
+    let sort_fields: Vec<&Field> = model
+        .fields
+        .iter()
+        .filter(|f| f.relation.is_none() && !f.directives.omit_order_by && is_groupable(f))
+        .collect();
+    let mut content = String::new();
+
+    content.push_str("import { builder } from \"../builder\";\n");
+    content.push_str("import { SortOrder } from \"../enums\";\n");
+    if nulls_order && sort_fields.iter().any(|f| is_nullable_sort_field(f)) {
+        content.push_str("import { SortOrderInput } from \"./filters\";\n");
+    }
+    for (_, input_name, _) in &aggregates {
+        content.push_str(&format!("import {{ {} }} from \"./{}\";\n", input_name, input_name));
+    }
+    content.push('\n');
+
+    let input_name = names.order_by_with_aggregation_input;
+
+    content.push_str(&format!(
+        "export const {} = builder.inputType(\"{}\", {{\n",
+        input_name, input_name
+    ));
+    content.push_str("  fields: (t) => ({\n");
 
+    for field in &sort_fields {
+        content.push_str(&format!("    {},\n", sort_field(field, nulls_order)));
+    }
+    for (key, aggregate_input, _) in &aggregates {
+        content.push_str(&format!("    {}: t.field({{ type: {} }}),\n", key, aggregate_input));
+    }
+
+    content.push_str("  }),\n");
+    content.push_str("});\n");
+
+    fs::write(dir.join(format!("{}.ts", input_name)), content)?;
+
+    for (_, aggregate_input, fields) in &aggregates {
+        let mut content = String::new();
+        content.push_str("import { builder } from \"../builder\";\n");
+        content.push_str("import { SortOrder } from \"../enums\";\n\n");
         content.push_str(&format!(
-            "    {}: t.field({{ type: SortOrder }}),\n",
-            field.name
+            "export const {} = builder.inputType(\"{}\", {{\n",
+            aggregate_input, aggregate_input
         ));
+        content.push_str("  fields: (t) => ({\n");
+        for field in fields {
+            content.push_str(&format!("    {},\n", typed_input_field(&field.name, "SortOrder", &input_options(field, false))));
+        }
+        content.push_str("  }),\n");
+        content.push_str("});\n");
+
+        fs::write(dir.join(format!("{}.ts", aggregate_input)), content)?;
     }
 
+    Ok(())
+}
+
+/// `_count`, `_avg`, `_sum`, `_min` and `_max` of OrderByWithAggregationInput, with their input
+/// (e.g. `PostAvgOrderByAggregateInput`) and the fields they sort by; aggregates without fields are left out
+pub fn aggregate_order_by_inputs(model: &Model) -> Vec<(&'static str, String, Vec<&Field>)> {
+    let fields: Vec<&Field> = model
+        .fields
+        .iter()
+        .filter(|f| f.relation.is_none() && !f.directives.omit_order_by && is_groupable(f))
+        .collect();
+    let single = |aggregate: fn(&Field) -> Option<String>| -> Vec<&Field> {
+        fields.iter().copied().filter(|f| !f.is_list && aggregate(f).is_some()).collect()
+    };
+
+    [
+        ("_count", "Count", fields.clone()),
+        ("_avg", "Avg", single(sum_field)),
+        ("_sum", "Sum", single(sum_field)),
+        ("_min", "Min", single(min_max_field)),
+        ("_max", "Max", single(min_max_field)),
+    ]
+    .into_iter()
+    .filter(|(_, _, fields)| !fields.is_empty())
+    .map(|(key, kind, fields)| (key, format!("{}{}OrderByAggregateInput", model.name, kind), fields))
+    .collect()
+}
+
+/// Generate OrderByRelationAggregateInput, used to sort by the number of records of a list relation
+fn generate_order_by_relation_aggregate_input(model: &Model, dir: &Path) -> Result<()> {
+    let mut content = String::new();
+
+    content.push_str("import { builder } from \"../builder\";\n");
+    content.push_str("import { SortOrder } from \"../enums\";\n\n");
+
+    let input_name = format!("{}OrderByRelationAggregateInput", model.name);
+
+    content.push_str(&format!(
+        "export const {} = builder.inputType(\"{}\", {{\n",
+        input_name, input_name
+    ));
+    content.push_str("  fields: (t) => ({\n");
+    content.push_str("    _count: t.field({ type: SortOrder }),\n");
     content.push_str("  }),\n");
     content.push_str("});\n");
 
@@ -446,242 +901,208 @@ fn generate_order_by_input(model: &Model, dir: &Path) -> Result<()> {
     Ok(())
 }
 
+/// Nullable columns take `{ sort, nulls }` where the provider can place nulls first or last
+fn sort_field(field: &Field, nulls_order: bool) -> String {
+    let type_name = if nulls_order && is_nullable_sort_field(field) { "SortOrderInput" } else { "SortOrder" };
+    typed_input_field(&field.name, type_name, &input_options(field, false))
+}
+
+fn is_nullable_sort_field(field: &Field) -> bool {
+    !field.is_required && !field.is_list
+}
+
+fn has_nullable_sort_field(model: &Model) -> bool {
+    model
+        .fields
+        .iter()
+        .any(|f| f.relation.is_none() && !f.directives.omit_order_by && is_nullable_sort_field(f))
+}
+
+/// Whether a scalar field must be sent on create: ids, defaults and `@updatedAt` are filled in by Prisma
+pub fn create_required(field: &Field) -> bool {
+    field.is_required && !field.is_id && !field.is_updated_at && field.default_value.is_none()
+}
+
+/// Options of a generated input field: `required: true` and the field's description
+pub fn input_options(field: &Field, required: bool) -> String {
+    let mut options = Vec::new();
+    if required {
+        options.push("required: true".to_string());
+    }
+    if let Some(description) = &field.documentation {
+        options.push(format!("description: {}", js_string(description)));
+    }
+    options.join(", ")
+}
+
+/// `{field}Null`, the way to store `Prisma.DbNull` or `Prisma.JsonNull` in an optional Json field
+/// The sentinels cannot share the `JSON` field, where "DbNull" is a valid string
+pub fn json_null_field(field: &Field) -> Option<String> {
+    (matches!(field.field_type, FieldType::Json) && !field.is_list && !field.is_required)
+        .then(|| format!("{}Null: t.field({{ type: NullableJsonNullValueInput }})", field.name))
+}
+
+/// Fail when a `{field}Null` input of an optional Json field would clash with another field of the model
+pub fn check_json_null_fields(schema: &ParsedSchema) -> Result<()> {
+    for model in &schema.models {
+        for field in model.fields.iter().filter(|f| json_null_field(f).is_some()) {
+            let null_name = format!("{}Null", field.name);
+            if model.fields.iter().any(|f| f.name == null_name) {
+                bail!(
+                    "field `{}.{}` clashes with the `{}` input of the Json field `{}`",
+                    model.name,
+                    null_name,
+                    null_name,
+                    field.name
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+/// `name: t.field({ type: Type })` with extra options
+fn typed_input_field(name: &str, type_ref: &str, options: &str) -> String {
+    if options.is_empty() {
+        format!("{}: t.field({{ type: {} }})", name, type_ref)
+    } else {
+        format!("{}: t.field({{ type: {}, {} }})", name, type_ref, options)
+    }
+}
+
 /// Generate a single input field code
-fn generate_input_field(field_type: &FieldType, name: &str, is_list: bool, required: &str) -> String {
-    let required_opt = if required.is_empty() { "".to_string() } else { format!(", {}", required) };
+pub fn generate_input_field(field_type: &FieldType, name: &str, is_list: bool, options: &str) -> String {
+    let options_suffix = if options.is_empty() { "".to_string() } else { format!(", {}", options) };
 
     match field_type {
         FieldType::String => {
             if is_list {
-                format!("{}: t.stringList({{{}}})", name, required)
+                format!("{}: t.stringList({{{}}})", name, options)
             } else {
-                format!("{}: t.string({{{}}})", name, required)
+                format!("{}: t.string({{{}}})", name, options)
             }
         }
         FieldType::Int => {
             if is_list {
-                format!("{}: t.intList({{{}}})", name, required)
+                format!("{}: t.intList({{{}}})", name, options)
             } else {
-                format!("{}: t.int({{{}}})", name, required)
+                format!("{}: t.int({{{}}})", name, options)
             }
         }
         FieldType::Float => {
             if is_list {
-                format!("{}: t.field({{ type: [\"Float\"]{}}})", name, required_opt)
+                format!("{}: t.field({{ type: [\"Float\"]{}}})", name, options_suffix)
             } else {
-                format!("{}: t.float({{{}}})", name, required)
+                format!("{}: t.float({{{}}})", name, options)
             }
         }
         FieldType::Boolean => {
             if is_list {
-                format!("{}: t.booleanList({{{}}})", name, required)
+                format!("{}: t.booleanList({{{}}})", name, options)
             } else {
-                format!("{}: t.boolean({{{}}})", name, required)
+                format!("{}: t.boolean({{{}}})", name, options)
             }
         }
         FieldType::DateTime => {
             if is_list {
-                format!("{}: t.field({{ type: [\"DateTime\"]{}}})", name, required_opt)
+                format!("{}: t.field({{ type: [\"DateTime\"]{}}})", name, options_suffix)
             } else {
-                format!("{}: t.field({{ type: \"DateTime\"{}}})", name, required_opt)
+                format!("{}: t.field({{ type: \"DateTime\"{}}})", name, options_suffix)
             }
         }
         FieldType::Json => {
             if is_list {
-                format!("{}: t.field({{ type: [\"JSON\"]{}}})", name, required_opt)
+                format!("{}: t.field({{ type: [\"JSON\"]{}}})", name, options_suffix)
             } else {
-                format!("{}: t.field({{ type: \"JSON\"{}}})", name, required_opt)
+                format!("{}: t.field({{ type: \"JSON\"{}}})", name, options_suffix)
             }
         }
         FieldType::Decimal => {
-            // Decimal input as float
             if is_list {
-                format!("{}: t.field({{ type: [\"Float\"]{}}})", name, required_opt)
+                format!("{}: t.field({{ type: [\"Decimal\"]{}}})", name, options_suffix)
             } else {
-                format!("{}: t.float({{{}}})", name, required)
+                format!("{}: t.field({{ type: \"Decimal\"{}}})", name, options_suffix)
             }
         }
         FieldType::BigInt => {
-            // BigInt input as string
             if is_list {
-                format!("{}: t.stringList({{{}}})", name, required)
+                format!("{}: t.field({{ type: [\"BigInt\"]{}}})", name, options_suffix)
             } else {
-                format!("{}: t.string({{{}}})", name, required)
+                format!("{}: t.field({{ type: \"BigInt\"{}}})", name, options_suffix)
             }
         }
         FieldType::Bytes => {
-            // Bytes input as string (base64)
             if is_list {
-                format!("{}: t.stringList({{{}}})", name, required)
+                format!("{}: t.field({{ type: [\"Bytes\"]{}}})", name, options_suffix)
             } else {
-                format!("{}: t.string({{{}}})", name, required)
+                format!("{}: t.field({{ type: \"Bytes\"{}}})", name, options_suffix)
             }
         }
         FieldType::Enum(enum_name) => {
             if is_list {
-                format!("{}: t.field({{ type: [{}]{}}})", name, enum_name, required_opt)
+                format!("{}: t.field({{ type: [{}]{}}})", name, enum_name, options_suffix)
             } else {
-                format!("{}: t.field({{ type: {}{}}})", name, enum_name, required_opt)
+                format!("{}: t.field({{ type: {}{}}})", name, enum_name, options_suffix)
             }
         }
         FieldType::Model(_) => {
             // This shouldn't happen for input fields
-            format!("{}: t.string({{{}}})", name, required)
+            format!("{}: t.string({{{}}})", name, options)
         }
     }
 }
 
-fn get_filter_type(field_type: &FieldType) -> String {
-    match field_type {
-        FieldType::String => "StringFilter".to_string(),
-        FieldType::Int => "IntFilter".to_string(),
-        FieldType::Float => "FloatFilter".to_string(),
-        FieldType::Boolean => "BoolFilter".to_string(),
-        FieldType::DateTime => "DateTimeFilter".to_string(),
-        FieldType::Json => "\"JSON\"".to_string(),
-        FieldType::Decimal => "FloatFilter".to_string(),
-        FieldType::BigInt => "StringFilter".to_string(),
-        FieldType::Bytes => "StringFilter".to_string(),
-        FieldType::Enum(_) => "StringFilter".to_string(),
-        FieldType::Model(_) => "StringFilter".to_string(),
+/// Filter input of a scalar field: `StringFilter`, or `StringNullableFilter` for optional fields
+/// Enum fields get their own `Enum{Name}Filter` family, and lists a `{Type}NullableListFilter`
+/// With `aggregates`, the `having` counterpart (`StringWithAggregatesFilter`...)
+fn get_filter_type(field: &Field, aggregates: bool) -> String {
+    let base = filter_base(&field.field_type);
+    if field.is_list {
+        format!("{}NullableListFilter", base)
+    } else {
+        filter_name(&base, !field.is_required, aggregates)
     }
 }
 
-/// Generate WhereUnique input for relations (used in connect operations)
-/// This is similar to the regular WhereUniqueInput but specifically for relation operations
-fn generate_where_unique_input_for_relations(model: &Model, dir: &Path) -> Result<()> {
-    let _names = get_prisma_name(&model.name);
-    let mut content = String::new();
-
-    content.push_str("import { builder } from \"../builder\";\n\n");
-
-    let input_name = format!("{}WhereUniqueRelationInput", model.name);
-
-    content.push_str(&format!(
-        "export const {} = builder.inputType(\"{}\", {{\n",
-        input_name, input_name
-    ));
-    content.push_str("  fields: (t) => ({\n");
-
-    // ID and unique fields only
-    for field in &model.fields {
-        if field.is_id || field.is_unique {
-            if field.relation.is_some() {
-                continue; // Skip relation fields in WhereUnique
-            }
-            let field_code = generate_input_field(&field.field_type, &field.name, false, "");
-            content.push_str(&format!("    {},\n", field_code));
-        }
+/// Prefix of the filter and update operations inputs of a field type, e.g. `Bool` or `EnumRole`
+fn filter_base(field_type: &FieldType) -> String {
+    match field_type {
+        FieldType::String => "String".to_string(),
+        FieldType::Int => "Int".to_string(),
+        FieldType::Float => "Float".to_string(),
+        FieldType::Boolean => "Bool".to_string(),
+        FieldType::DateTime => "DateTime".to_string(),
+        FieldType::Json => "Json".to_string(),
+        FieldType::Decimal => "Decimal".to_string(),
+        FieldType::BigInt => "BigInt".to_string(),
+        FieldType::Bytes => "Bytes".to_string(),
+        FieldType::Enum(enum_name) => format!("Enum{}", enum_name),
+        FieldType::Model(_) => "String".to_string(),
     }
-
-    content.push_str("  }),\n");
-    content.push_str("});\n");
-
-    fs::write(dir.join(format!("{}.ts", input_name)), content)?;
-
-    Ok(())
 }
 
-/// Generate RelationCreate input (like CreateInput but without reverse relations to avoid circular deps)
-fn generate_relation_create_input(model: &Model, dir: &Path) -> Result<()> {
-    let _names = get_prisma_name(&model.name);
-    let mut content = String::new();
-    let used_enums = collect_enum_types(model);
-
-    content.push_str("import { builder } from \"../builder\";\n");
-    if !used_enums.is_empty() {
-        let enum_imports: Vec<String> = used_enums.into_iter().collect();
-        content.push_str(&format!("import {{ {} }} from \"../enums\";\n", enum_imports.join(", ")));
+/// `import { ... } from "./filters"` for the filters used by these fields
+fn filter_imports(fields: &[&Field], aggregates: bool) -> String {
+    let mut filters: Vec<String> = fields
+        .iter()
+        .map(|f| get_filter_type(f, aggregates))
+        .collect();
+    filters.sort();
+    filters.dedup();
+
+    if filters.is_empty() {
+        String::new()
+    } else {
+        format!("import {{ {} }} from \"./filters\";\n", filters.join(", "))
     }
-    
-    // Import relation input types for nested relations
-    let mut relation_imports: Vec<String> = Vec::new();
-    for field in &model.fields {
-        if let Some(_relation) = &field.relation {
-            if let FieldType::Model(related_model) = &field.field_type {
-                let relation_input_name = if field.is_list {
-                    format!("{}{}ListRelationInput", model.name, related_model)
-                } else {
-                    format!("{}{}RelationInput", model.name, related_model)
-                };
-                if !relation_imports.contains(&relation_input_name) {
-                    relation_imports.push(relation_input_name);
-                }
-            }
-        }
-    }
-    
-    if !relation_imports.is_empty() {
-        content.push_str(&format!("import {{ {} }} from \"./relations\";\n", relation_imports.join(", ")));
-    }
-    
-    content.push('\n');
-
-    let input_name = format!("{}RelationCreateInput", model.name);
-
-    content.push_str(&format!(
-        "export const {} = builder.inputType(\"{}\", {{\n",
-        input_name, input_name
-    ));
-    content.push_str("  fields: (t) => ({\n");
-
-    // Collect all foreign keys to skip them
-    let mut foreign_keys: Vec<String> = Vec::new();
-    for field in &model.fields {
-        if let Some(relation) = &field.relation {
-            if !relation.fields.is_empty() {
-                foreign_keys.extend(relation.fields.clone());
-            }
-        }
-    }
-
-    for field in &model.fields {
-        // Skip auto-generated fields and foreign keys
-        if field.is_updated_at || field.name == "created_at" || field.name == "updated_at" || foreign_keys.contains(&field.name) {
-            continue;
-        }
-
-        // For relation fields, add the nested relation input
-        if let Some(relation) = &field.relation {
-            // Include only relations WITH fields (the "owning" side with foreign key)
-            // These are the ones where we can connect/create related records
-            // Skip reverse relations (without fields) to avoid duplication
-            if relation.fields.is_empty() {
-                continue;
-            }
-            
-            if let FieldType::Model(related_model) = &field.field_type {
-                let relation_input_type = if field.is_list {
-                    format!("{}{}ListRelationInput", model.name, related_model)
-                } else {
-                    format!("{}{}RelationInput", model.name, related_model)
-                };
-                let required_option = if field.is_required { ", required: true" } else { "" };
-                content.push_str(&format!(
-                    "    {}: t.field({{ type: {}{} }}),\n",
-                    field.name, relation_input_type, required_option
-                ));
-            }
-            continue;
-        }
-
-        let required = if field.is_id || !field.is_required || field.default_value.is_some() {
-            ""
-        } else {
-            "required: true"
-        };
-
-        let field_code = generate_input_field(&field.field_type, &field.name, field.is_list, required);
-        content.push_str(&format!("    {},\n", field_code));
-    }
-
-    content.push_str("  }),\n");
-    content.push_str("});\n");
-
-    fs::write(dir.join(format!("{}.ts", input_name)), content)?;
+}
 
-    Ok(())
+/// Generate WhereUnique input for relations (used in connect operations)
+/// This is similar to the regular WhereUniqueInput but specifically for relation operations
+fn generate_where_unique_input_for_relations(model: &Model, dir: &Path) -> Result<()> {
+    generate_unique_selector_input(model, &format!("{}WhereUniqueRelationInput", model.name), dir)
 }
+
 /// Generate RelationFilter input type for a model (used in WhereInput for relation filtering)
 fn generate_relation_filter(model: &Model, dir: &Path) -> Result<()> {
     let names = get_prisma_name(&model.name);
@@ -732,3 +1153,52 @@ fn generate_list_relation_filter(model: &Model, dir: &Path) -> Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::diagnostics::{Diagnostics, SourceMap};
+    use crate::parser::parse_schema;
+
+    fn parse(content: &str) -> ParsedSchema {
+        let mut sources = SourceMap::new();
+        sources.add("schema.prisma", content);
+        let mut diagnostics = Diagnostics::new();
+        parse_schema(&sources, &mut diagnostics).expect("schema should parse")
+    }
+
+    #[test]
+    fn nested_create_leaves_updated_at_optional() {
+        let schema = parse(
+            r#"
+model User {
+  id        Int      @id @default(autoincrement())
+  email     String
+  updatedAt DateTime @updatedAt
+  posts     Post[]
+}
+
+model Post {
+  id       Int  @id @default(autoincrement())
+  authorId Int
+  author   User @relation(fields: [authorId], references: [id])
+}
+"#,
+        );
+        let user = &schema.models[0];
+        let post = &schema.models[1];
+        let back = post.fields.iter().find(|f| f.name == "author").unwrap();
+        let user_posts = user.fields.iter().find(|f| f.name == "posts").unwrap();
+
+        // `UserCreateWithoutPostsInput`
+        let fields = create_input_fields(user, Some(user_posts));
+        let updated_at = fields.iter().find(|f| f.contains("updatedAt")).expect("updatedAt should be writable");
+        assert!(!updated_at.contains("required: true"), "{updated_at}");
+        assert!(fields.iter().any(|f| f.contains("email") && f.contains("required: true")));
+        assert!(!fields.iter().any(|f| f.contains("posts")));
+
+        // `PostCreateWithoutAuthorInput` drops the relation back and its foreign key
+        let fields = create_input_fields(post, Some(back));
+        assert!(!fields.iter().any(|f| f.contains("author")));
+    }
+}
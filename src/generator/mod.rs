@@ -1,3 +1,4 @@
+pub mod aggregates;
 pub mod enums;
 pub mod filters;
 pub mod helpers;
@@ -8,13 +9,33 @@ pub mod resolvers;
 
 pub use helpers::get_prisma_name;
 
-use crate::parser::ParsedSchema;
+use crate::config::Config;
+use crate::parser::{FieldType, ParsedSchema};
 use anyhow::Result;
 use std::fs;
 use std::path::Path;
 
 /// Generate all Pothos code from parsed schema
-pub fn generate(schema: &ParsedSchema, output_dir: &Path, manual_resolvers: &crate::scanner::ManualResolvers, verbose: bool) -> Result<()> {
+pub fn generate(schema: &ParsedSchema, output_dir: &Path, manual_resolvers: &crate::scanner::ManualResolvers, config: &Config) -> Result<()> {
+    let verbose = config.verbose;
+
+    // Drop the models excluded in the config
+    let schema = &select_models(schema, config);
+
+    // Relation inputs and Json null inputs are named after their fields; stop before writing anything if two collide
+    relations::check_relation_inputs(schema)?;
+    inputs::check_json_null_fields(schema)?;
+
+    // Models without a unique field or key cannot be paginated with Relay cursors
+    if config.relay {
+        for model in schema.models.iter().filter(|m| helpers::relay_cursor(m).is_none()) {
+            eprintln!(
+                "warning: model `{}` has no visible unique field or key for Relay cursors; its findMany and list relations stay plain lists",
+                model.name
+            );
+        }
+    }
+
     // Create output directories
     create_directories(output_dir)?;
 
@@ -22,7 +43,7 @@ pub fn generate(schema: &ParsedSchema, output_dir: &Path, manual_resolvers: &cra
     if verbose {
         println!("Generating builder...");
     }
-    helpers::generate_helpers(output_dir)?;
+    helpers::generate_helpers(output_dir, config.relay)?;
 
     // Generate enums
     if verbose {
@@ -30,12 +51,16 @@ pub fn generate(schema: &ParsedSchema, output_dir: &Path, manual_resolvers: &cra
     }
     enums::generate_base_enums(output_dir)?;
     enums::generate_schema_enums(schema, output_dir)?;
+    enums::generate_scalar_field_enums(schema, output_dir)?;
 
     // Generate filters
     if verbose {
         println!("Generating filters...");
     }
-    filters::generate_filters(output_dir)?;
+    filters::generate_filters(schema, output_dir)?;
+    if config.field_update_operations {
+        filters::generate_update_operations(schema, output_dir)?;
+    }
 
     // Generate per-model files
     for model in &schema.models {
@@ -43,23 +68,40 @@ pub fn generate(schema: &ParsedSchema, output_dir: &Path, manual_resolvers: &cra
             println!("Generating for model: {}", model.name);
         }
 
-        models::generate_model(model, output_dir)?;
-        inputs::generate_inputs(model, output_dir)?;
-        resolvers::generate_resolvers(model, schema, output_dir, manual_resolvers, verbose)?;
+        models::generate_model(model, schema, output_dir, config.relay)?;
+        aggregates::generate_aggregates(model, output_dir)?;
+        inputs::generate_inputs(model, schema, output_dir, config.field_update_operations)?;
+        resolvers::generate_resolvers(model, schema, output_dir, manual_resolvers, config)?;
     }
 
     // Generate relation inputs (must be after all models are processed)
     if verbose {
         println!("Generating relation inputs...");
     }
-    relations::generate_all_relation_inputs(schema, output_dir)?;
+    relations::generate_all_relation_inputs(schema, output_dir, config.field_update_operations)?;
 
     // Generate index file
-    generate_index(schema, output_dir, manual_resolvers)?;
+    generate_index(schema, output_dir, manual_resolvers, config)?;
 
     Ok(())
 }
 
+/// Keep the models selected by `models.include`/`models.exclude`, without relation fields
+/// pointing to models that are left out
+fn select_models(schema: &ParsedSchema, config: &Config) -> ParsedSchema {
+    let mut selected = schema.clone();
+    selected.models.retain(|m| config.includes_model(&m.name));
+
+    for model in &mut selected.models {
+        model.fields.retain(|f| match &f.field_type {
+            FieldType::Model(related) => config.includes_model(related),
+            _ => true,
+        });
+    }
+
+    selected
+}
+
 /// Run as a Prisma generator (reads DMMF from stdin)
 pub fn run_as_prisma_generator() -> Result<()> {
     use std::io::{self, BufRead, Write};
@@ -105,10 +147,19 @@ pub fn run_as_prisma_generator() -> Result<()> {
 
 
                         // Parse DMMF and generate
-                        let schema = parse_dmmf(dmmf)?;
+                        let mut schema = parse_dmmf(dmmf)?;
+                        parse_generator_options(params, &mut schema);
+
+                        // The request only carries this generator's block, whatever its provider is called
+                        let mut config = Config::load()?;
+                        if let Some(generator) = schema.generators.first() {
+                            config.merge_options(&generator.config)?;
+                        }
+                        config.check_models(&schema)?;
+
                         // In prisma generator mode, we don't scan for manual resolvers
                         let manual_resolvers = crate::scanner::ManualResolvers::new();
-                        generate(&schema, Path::new(output_path), &manual_resolvers, false)?;
+                        generate(&schema, Path::new(output_path), &manual_resolvers, &config)?;
                     }
                 }
 
@@ -129,7 +180,8 @@ pub fn run_as_prisma_generator() -> Result<()> {
 
 /// Parse Prisma DMMF (Data Model Meta Format) into our ParsedSchema
 fn parse_dmmf(dmmf: &serde_json::Value) -> Result<ParsedSchema> {
-    use crate::parser::{Enum, EnumValue, Field, FieldType, Model, PrimaryKey, Relation};
+    use crate::parser::directives::parse_documentation;
+    use crate::parser::{Enum, EnumValue, Field, FieldType, Index, Model, PrimaryKey, Relation};
 
     let mut models = Vec::new();
     let mut enums = Vec::new();
@@ -147,6 +199,7 @@ fn parse_dmmf(dmmf: &serde_json::Value) -> Result<ParsedSchema> {
                                 Some(EnumValue {
                                     name: v.get("name")?.as_str()?.to_string(),
                                     db_name: v.get("dbName").and_then(|d| d.as_str()).map(String::from),
+                                    documentation: v.get("documentation").and_then(|d| d.as_str()).map(String::from),
                                 })
                             })
                             .collect()
@@ -225,6 +278,15 @@ fn parse_dmmf(dmmf: &serde_json::Value) -> Result<ParsedSchema> {
                                     None
                                 };
 
+                                let (documentation, directives, errors) = f
+                                    .get("documentation")
+                                    .and_then(|d| d.as_str())
+                                    .map(parse_documentation)
+                                    .unwrap_or_default();
+                                for error in errors {
+                                    eprintln!("warning: {}.{}: {}", name, field_name, error.message);
+                                }
+
                                 Some(Field {
                                     name: field_name,
                                     field_type,
@@ -235,6 +297,8 @@ fn parse_dmmf(dmmf: &serde_json::Value) -> Result<ParsedSchema> {
                                     is_updated_at,
                                     default_value: f.get("default").map(|d| d.to_string()),
                                     relation,
+                                    documentation,
+                                    directives,
                                 })
                             })
                             .collect()
@@ -264,18 +328,76 @@ fn parse_dmmf(dmmf: &serde_json::Value) -> Result<ParsedSchema> {
                     }
                 });
 
+                // Extract compound unique constraints
+                let unique_fields: Vec<Index> = m
+                    .get("uniqueIndexes")
+                    .and_then(|u| u.as_array())
+                    .map(|arr| {
+                        arr.iter()
+                            .map(|u| Index {
+                                fields: u
+                                    .get("fields")
+                                    .and_then(|f| f.as_array())
+                                    .map(|arr| arr.iter().filter_map(|v| v.as_str().map(String::from)).collect())
+                                    .unwrap_or_default(),
+                                name: u.get("name").and_then(|n| n.as_str()).map(String::from),
+                            })
+                            .collect()
+                    })
+                    .unwrap_or_default();
+
                 models.push(Model {
                     name,
                     db_name,
                     fields,
                     primary_key,
-                    unique_fields: Vec::new(),
+                    unique_fields,
+                    indexes: Vec::new(),
+                    documentation: m.get("documentation").and_then(|d| d.as_str()).map(String::from),
                 });
             }
         }
     }
 
-    Ok(ParsedSchema { models, enums })
+    Ok(ParsedSchema {
+        models,
+        enums,
+        datasource: None,
+        generators: Vec::new(),
+    })
+}
+
+/// Read the datasource provider and this generator's block from the `generate` request params
+fn parse_generator_options(params: &serde_json::Value, schema: &mut ParsedSchema) {
+    use crate::parser::{Datasource, Generator, Provider};
+
+    schema.datasource = params
+        .get("datasources")
+        .and_then(|d| d.as_array())
+        .and_then(|d| d.first())
+        .and_then(|d| {
+            let provider = d.get("activeProvider").or_else(|| d.get("provider"))?.as_str()?;
+            Some(Datasource {
+                name: d.get("name").and_then(|n| n.as_str()).unwrap_or("db").to_string(),
+                provider: Provider::from_name(provider)?,
+            })
+        });
+
+    if let Some(generator) = params.get("generator") {
+        let value = |key: &str| {
+            generator
+                .get(key)
+                .and_then(|v| v.get("value"))
+                .and_then(|v| v.as_str())
+                .map(String::from)
+        };
+        schema.generators.push(Generator {
+            name: generator.get("name").and_then(|n| n.as_str()).unwrap_or("pothos").to_string(),
+            provider: value("provider"),
+            output: value("output"),
+            config: generator.get("config").and_then(|c| c.as_object()).cloned().unwrap_or_default(),
+        });
+    }
 }
 
 fn create_directories(output_dir: &Path) -> Result<()> {
@@ -294,7 +416,7 @@ fn create_directories(output_dir: &Path) -> Result<()> {
     Ok(())
 }
 
-fn generate_index(schema: &ParsedSchema, output_dir: &Path, manual_resolvers: &crate::scanner::ManualResolvers) -> Result<()> {
+fn generate_index(schema: &ParsedSchema, output_dir: &Path, manual_resolvers: &crate::scanner::ManualResolvers, config: &Config) -> Result<()> {
     let mut content = String::new();
 
     content.push_str("// Auto-generated by prisma-pothos-generator\n");
@@ -308,11 +430,16 @@ fn generate_index(schema: &ParsedSchema, output_dir: &Path, manual_resolvers: &c
     content.push_str("export * from './enums';\n\n");
 
     content.push_str("// Filters\n");
-    content.push_str("export * from './inputs/filters';\n\n");
+    content.push_str("export * from './inputs/filters';\n");
+    if config.field_update_operations {
+        content.push_str("export * from './inputs/updateOperations';\n");
+    }
+    content.push('\n');
 
     content.push_str("// Models\n");
     for model in &schema.models {
         content.push_str(&format!("export * from './models/{}';\n", model.name));
+        content.push_str(&format!("export * from './models/{}Aggregates';\n", model.name));
     }
 
     content.push_str("\n// Inputs\n");
@@ -321,40 +448,25 @@ fn generate_index(schema: &ParsedSchema, output_dir: &Path, manual_resolvers: &c
         content.push_str(&format!("export * from './inputs/{}';\n", names.create_input));
         content.push_str(&format!("export * from './inputs/{}';\n", names.create_many_input));
         content.push_str(&format!("export * from './inputs/{}';\n", names.update_input));
+        content.push_str(&format!("export * from './inputs/{}';\n", names.update_many_input));
+        for field in inputs::list_update_fields(model) {
+            content.push_str(&format!("export * from './inputs/{}';\n", inputs::list_update_input_name(model, field)));
+        }
         content.push_str(&format!("export * from './inputs/{}';\n", names.where_input));
+        content.push_str(&format!("export * from './inputs/{}';\n", names.scalar_where_with_aggregates_input));
         content.push_str(&format!("export * from './inputs/{}';\n", names.where_unique_input));
         content.push_str(&format!("export * from './inputs/{}';\n", names.order_by_input));
+        content.push_str(&format!("export * from './inputs/{}';\n", names.order_by_with_aggregation_input));
+        for (_, input_name, _) in inputs::aggregate_order_by_inputs(model) {
+            content.push_str(&format!("export * from './inputs/{}';\n", input_name));
+        }
     }
 
     content.push_str("\n// Resolvers\n");
     for model in &schema.models {
-        let names = get_prisma_name(&model.name);
-        
-        // Only export resolvers that were actually generated (not skipped)
-        // Note: createOne, createMany and updateOne are mutations, others are queries
-        if !manual_resolvers.contains_mutation(&names.create) {
-            content.push_str(&format!("export * from './resolvers/createOne{}';\n", model.name));
-        }
-        
-        if !manual_resolvers.contains_mutation(&names.create_many) {
-            content.push_str(&format!("export * from './resolvers/createMany{}';\n", model.name));
-        }
-        
-        if !manual_resolvers.contains_query(&names.find_many) {
-            content.push_str(&format!("export * from './resolvers/findMany{}';\n", model.name));
-        }
-        
-        if !manual_resolvers.contains_query(&names.find) {
-            content.push_str(&format!("export * from './resolvers/findUnique{}';\n", model.name));
-        }
-        
-        let aggregate_name = format!("aggregate{}", model.name);
-        if !manual_resolvers.contains_query(&aggregate_name) {
-            content.push_str(&format!("export * from './resolvers/aggregate{}';\n", model.name));
-        }
-        
-        if !manual_resolvers.contains_mutation(&names.update) {
-            content.push_str(&format!("export * from './resolvers/updateOne{}';\n", model.name));
+        // Only export resolvers that were actually generated (not disabled or written by hand)
+        for operation in resolvers::enabled_operations(model, config, manual_resolvers) {
+            content.push_str(&format!("export * from './resolvers/{}{}';\n", operation.name(), model.name));
         }
     }
 
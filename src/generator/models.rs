@@ -1,17 +1,21 @@
-use crate::parser::{FieldType, Model};
+use crate::generator::helpers::{has_json_nulls, js_string, json_nulls_import, prisma_arg, relay_cursor};
+use crate::parser::{Field, FieldType, Model, ParsedSchema};
 use anyhow::Result;
 use std::collections::HashSet;
 use std::fs;
 use std::path::Path;
 
 /// Generate Pothos PrismaObject type
-pub fn generate_model(model: &Model, output_dir: &Path) -> Result<()> {
+/// With `relay`, list relations are exposed as connections when the related model has a cursor field
+pub fn generate_model(model: &Model, schema: &ParsedSchema, output_dir: &Path, relay: bool) -> Result<()> {
     let models_dir = output_dir.join("models");
     fs::create_dir_all(&models_dir)?;
 
     // Collect all enum types used in this model
     let mut used_enums: HashSet<String> = HashSet::new();
-    for field in &model.fields {
+    let fields: Vec<&Field> = model.fields.iter().filter(|f| !f.directives.omit_output).collect();
+
+    for field in &fields {
         if let FieldType::Enum(enum_name) = &field.field_type {
             used_enums.insert(enum_name.clone());
         }
@@ -19,10 +23,16 @@ pub fn generate_model(model: &Model, output_dir: &Path) -> Result<()> {
 
     // Collect all related models for importing their input types
     let mut related_models: HashSet<String> = HashSet::new();
-    for field in &model.fields {
+    // Related models of list relations returned as plain lists, which take cursor and distinct args
+    let mut paginated_models: HashSet<String> = HashSet::new();
+    for field in &fields {
         if let Some(_relation) = &field.relation {
             if let FieldType::Model(related_model) = &field.field_type {
                 related_models.insert(related_model.clone());
+                if field.is_list && connection_cursor(schema, related_model, relay).is_none() {
+                    paginated_models.insert(related_model.clone());
+                    used_enums.insert(crate::generator::get_prisma_name(related_model).scalar_field_enum);
+                }
             }
         }
     }
@@ -30,11 +40,14 @@ pub fn generate_model(model: &Model, output_dir: &Path) -> Result<()> {
     let mut content = String::new();
 
     // Imports
-    content.push_str("import { builder } from \"../builder\";\n");
+    // Relation fields pass their `where` to Prisma
+    let json_nulls = has_json_nulls(schema) && fields.iter().any(|f| f.relation.is_some());
+    content.push_str(&format!("import {{ builder{} }} from \"../builder\";\n", json_nulls_import(json_nulls)));
 
     // Import used enums
     if !used_enums.is_empty() {
-        let enum_imports: Vec<String> = used_enums.into_iter().collect();
+        let mut enum_imports: Vec<String> = used_enums.into_iter().collect();
+        enum_imports.sort();
         content.push_str(&format!("import {{ {} }} from \"../enums\";\n", enum_imports.join(", ")));
     }
     
@@ -50,6 +63,12 @@ pub fn generate_model(model: &Model, output_dir: &Path) -> Result<()> {
                 "import {{ {} }} from \"../inputs/{}\";\n",
                 names.order_by_input, names.order_by_input
             ));
+            if paginated_models.contains(related_model) {
+                content.push_str(&format!(
+                    "import {{ {} }} from \"../inputs/{}\";\n",
+                    names.where_unique_input, names.where_unique_input
+                ));
+            }
         }
     }
     content.push('\n');
@@ -59,10 +78,13 @@ pub fn generate_model(model: &Model, output_dir: &Path) -> Result<()> {
         "export const {} = builder.prismaObject(\"{}\", {{\n",
         model.name, model.name
     ));
+    if let Some(description) = &model.documentation {
+        content.push_str(&format!("  description: {},\n", js_string(description)));
+    }
     content.push_str("  fields: (t) => ({\n");
 
     // Scalar fields
-    for field in &model.fields {
+    for field in &fields {
         if field.relation.is_some() {
             continue; // Relations handled separately
         }
@@ -72,7 +94,7 @@ pub fn generate_model(model: &Model, output_dir: &Path) -> Result<()> {
     }
 
     // Relation fields
-    for field in &model.fields {
+    for field in &fields {
         if field.relation.is_none() {
             continue;
         }
@@ -80,23 +102,48 @@ pub fn generate_model(model: &Model, output_dir: &Path) -> Result<()> {
         if field.is_list {
             if let FieldType::Model(related_model) = &field.field_type {
                 let names = crate::generator::get_prisma_name(related_model);
+                if let Some(cursor) = connection_cursor(schema, related_model, relay) {
+                    content.push_str(&format!(
+                        "    {}: t.relatedConnection(\"{}\", {{\n",
+                        field.output_name(), field.name
+                    ));
+                    push_description(&mut content, field);
+                    content.push_str(&format!("      cursor: \"{}\",\n", cursor));
+                    content.push_str("      totalCount: true,\n");
+                    content.push_str("      query: (args) => ({\n");
+                    content.push_str(&format!("        where: {} ?? undefined,\n", prisma_arg("where", json_nulls)));
+                    content.push_str("        orderBy: (args.orderBy ?? undefined) as any,\n");
+                    content.push_str("      }),\n");
+                    content.push_str("      args: {\n");
+                    content.push_str(&format!("        where: t.arg({{ type: {} }}),\n", names.where_input));
+                    content.push_str(&format!("        orderBy: t.arg({{ type: [{}] }}),\n", names.order_by_input));
+                    content.push_str("      },\n");
+                    content.push_str("    }),\n");
+                    continue;
+                }
+
                 content.push_str(&format!(
                     "    {}: t.relation(\"{}\", {{\n",
-                    field.name, field.name
+                    field.output_name(), field.name
                 ));
+                push_description(&mut content, field);
                 content.push_str("      nullable: false,\n");
                 content.push_str("      onNull: () => [],\n");
                 content.push_str("      query: (args) => ({\n");
-                content.push_str("        where: args.where,\n");
-                content.push_str("        take: args.first,\n");
-                content.push_str("        skip: args.last ? -args.last : undefined,\n");
-                content.push_str("        orderBy: args.orderBy,\n");
+                content.push_str(&format!("        where: {} ?? undefined,\n", prisma_arg("where", json_nulls)));
+                content.push_str("        orderBy: (args.orderBy ?? undefined) as any,\n");
+                content.push_str("        cursor: args.cursor ?? undefined,\n");
+                content.push_str("        take: args.take ?? undefined,\n");
+                content.push_str("        skip: args.skip ?? undefined,\n");
+                content.push_str("        distinct: args.distinct ?? undefined,\n");
                 content.push_str("      }),\n");
                 content.push_str("      args: {\n");
                 content.push_str(&format!("        where: t.arg({{ type: {} }}),\n", names.where_input));
-                content.push_str("        first: t.arg.int(),\n");
-                content.push_str("        last: t.arg.int(),\n");
                 content.push_str(&format!("        orderBy: t.arg({{ type: [{}] }}),\n", names.order_by_input));
+                content.push_str(&format!("        cursor: t.arg({{ type: {} }}),\n", names.where_unique_input));
+                content.push_str("        take: t.arg.int(),\n");
+                content.push_str("        skip: t.arg.int(),\n");
+                content.push_str(&format!("        distinct: t.arg({{ type: [{}] }}),\n", names.scalar_field_enum));
                 content.push_str("      },\n");
                 content.push_str("    }),\n");
             }
@@ -106,8 +153,9 @@ pub fn generate_model(model: &Model, output_dir: &Path) -> Result<()> {
                 let names = crate::generator::get_prisma_name(related_model);
                 content.push_str(&format!(
                     "    {}: t.relation(\"{}\", {{\n",
-                    field.name, field.name
+                    field.output_name(), field.name
                 ));
+                push_description(&mut content, field);
                 if field.is_required {
                     content.push_str("      nullable: false,\n");
                     content.push_str("      onNull: 'error',\n");
@@ -115,7 +163,7 @@ pub fn generate_model(model: &Model, output_dir: &Path) -> Result<()> {
                     content.push_str("      nullable: true,\n");
                 }
                 content.push_str("      query: (args) => ({\n");
-                content.push_str("        where: args.where,\n");
+                content.push_str(&format!("        where: {},\n", prisma_arg("where", json_nulls)));
                 content.push_str("      }),\n");
                 content.push_str("      args: {\n");
                 content.push_str(&format!("        where: t.arg({{ type: {} }}),\n", names.where_input));
@@ -133,124 +181,64 @@ pub fn generate_model(model: &Model, output_dir: &Path) -> Result<()> {
     Ok(())
 }
 
-fn generate_field_code(field: &crate::parser::Field) -> String {
-    let nullable_opt = if field.is_required { "" } else { "nullable: true" };
+/// Cursor of a list relation exposed as a Relay connection, if it is one
+fn connection_cursor(schema: &ParsedSchema, related_model: &str, relay: bool) -> Option<String> {
+    if !relay {
+        return None;
+    }
+    schema
+        .models
+        .iter()
+        .find(|m| m.name == related_model)
+        .and_then(relay_cursor)
+}
+
+fn push_description(content: &mut String, field: &Field) {
+    if let Some(description) = &field.documentation {
+        content.push_str(&format!("      description: {},\n", js_string(description)));
+    }
+}
+
+fn generate_field_code(field: &Field) -> String {
+    let name = &field.name;
+    let output_name = field.output_name();
+
+    let mut options = Vec::new();
+    if !field.is_required {
+        options.push("nullable: true".to_string());
+    } else if !field.is_list {
+        options.push("nullable: false".to_string());
+    }
+    if let Some(description) = &field.documentation {
+        options.push(format!("description: {}", js_string(description)));
+    }
+
+    // Simple expose methods for basic types
+    let expose = |method: &str| {
+        let list = if field.is_list { "List" } else { "" };
+        format!("{}: t.{}{}(\"{}\", {{ {} }})", output_name, method, list, name, options.join(", "))
+    };
+
+    // Types that need explicit type specification
+    let typed = |type_name: &str| {
+        let type_ref = if field.is_list { format!("[{}]", type_name) } else { type_name.to_string() };
+        let mut parts = vec![format!("type: {}", type_ref), format!("resolve: (parent) => parent.{}", name)];
+        parts.extend(options.iter().cloned());
+        format!("{}: t.field({{ {} }})", output_name, parts.join(", "))
+    };
 
     match &field.field_type {
-        // Simple expose methods for basic types
-        FieldType::String => {
-            if field.is_list {
-                format!("{}: t.exposeStringList(\"{}\", {{ {} }})",
-                    field.name, field.name, nullable_opt)
-            } else if field.is_id {
-                format!("{}: t.exposeString(\"{}\"{})",
-                    field.name, field.name,
-                    if !field.is_required { ", { nullable: true }" } else { ", { nullable: false }" })
-            } else {
-                format!("{}: t.exposeString(\"{}\"{})",
-                    field.name, field.name,
-                    if !field.is_required { ", { nullable: true }" } else { ", { nullable: false }" })
-            }
-        }
-        FieldType::Int => {
-            if field.is_list {
-                format!("{}: t.exposeIntList(\"{}\", {{ {} }})",
-                    field.name, field.name, nullable_opt)
-            } else {
-                format!("{}: t.exposeInt(\"{}\"{})",
-                    field.name, field.name,
-                    if !field.is_required { ", { nullable: true }" } else { ", { nullable: false }" })
-            }
-        }
-        FieldType::Float => {
-            if field.is_list {
-                format!("{}: t.exposeFloatList(\"{}\", {{ {} }})",
-                    field.name, field.name, nullable_opt)
-            } else {
-                format!("{}: t.exposeFloat(\"{}\"{})",
-                    field.name, field.name,
-                    if !field.is_required { ", { nullable: true }" } else { ", { nullable: false }" })
-            }
-        }
-        FieldType::Boolean => {
-            if field.is_list {
-                format!("{}: t.exposeBooleanList(\"{}\", {{ {} }})",
-                    field.name, field.name, nullable_opt)
-            } else {
-                format!("{}: t.exposeBoolean(\"{}\"{})",
-                    field.name, field.name,
-                    if !field.is_required { ", { nullable: true }" } else { ", { nullable: false }" })
-            }
-        }
-        // Types that need explicit type specification
-        FieldType::DateTime => {
-            if field.is_list {
-                format!("{}: t.field({{ type: [\"DateTime\"], resolve: (parent) => parent.{}, {} }})",
-                    field.name, field.name, nullable_opt)
-            } else {
-                format!("{}: t.field({{ type: \"DateTime\", resolve: (parent) => parent.{}{} }})",
-                    field.name, field.name,
-                    if !field.is_required { ", nullable: true" } else { ", nullable: false" })
-            }
-        }
-        FieldType::Json => {
-            if field.is_list {
-                format!("{}: t.field({{ type: [\"JSON\"], resolve: (parent) => parent.{}, {} }})",
-                    field.name, field.name, nullable_opt)
-            } else {
-                format!("{}: t.field({{ type: \"JSON\", resolve: (parent) => parent.{}{} }})",
-                    field.name, field.name,
-                    if !field.is_required { ", nullable: true" } else { ", nullable: false" })
-            }
-        }
-        FieldType::Decimal => {
-            // Decimal typically serializes to string
-            if field.is_list {
-                format!("{}: t.field({{ type: [\"String\"], resolve: (parent) => parent.{}?.map(d => d.toString()), {} }})",
-                    field.name, field.name, nullable_opt)
-            } else {
-                format!("{}: t.field({{ type: \"String\", resolve: (parent) => parent.{}?.toString(){} }})",
-                    field.name, field.name,
-                    if !field.is_required { ", nullable: true" } else { ", nullable: false" })
-            }
-        }
-        FieldType::BigInt => {
-            // BigInt serializes to string
-            if field.is_list {
-                format!("{}: t.field({{ type: [\"String\"], resolve: (parent) => parent.{}?.map(b => b.toString()), {} }})",
-                    field.name, field.name, nullable_opt)
-            } else {
-                format!("{}: t.field({{ type: \"String\", resolve: (parent) => parent.{}?.toString(){} }})",
-                    field.name, field.name,
-                    if !field.is_required { ", nullable: true" } else { ", nullable: false" })
-            }
-        }
-        FieldType::Bytes => {
-            // Bytes serializes to base64 string
-            if field.is_list {
-                format!("{}: t.field({{ type: [\"String\"], resolve: (parent) => parent.{}?.map(b => b.toString('base64')), {} }})",
-                    field.name, field.name, nullable_opt)
-            } else {
-                format!("{}: t.field({{ type: \"String\", resolve: (parent) => parent.{}?.toString('base64'){} }})",
-                    field.name, field.name,
-                    if !field.is_required { ", nullable: true" } else { ", nullable: false" })
-            }
-        }
-        FieldType::Enum(enum_name) => {
-            if field.is_list {
-                format!("{}: t.field({{ type: [{}], resolve: (parent) => parent.{}, {} }})",
-                    field.name, enum_name, field.name, nullable_opt)
-            } else {
-                format!("{}: t.field({{ type: {}, resolve: (parent) => parent.{}{} }})",
-                    field.name, enum_name, field.name,
-                    if !field.is_required { ", nullable: true" } else { ", nullable: false" })
-            }
-        }
-        FieldType::Model(_) => {
-            // This shouldn't happen for non-relation fields, but handle it
-            format!("{}: t.exposeString(\"{}\"{})",
-                field.name, field.name,
-                if !field.is_required { ", { nullable: true }" } else { ", { nullable: false }" })
-        }
+        FieldType::String => expose("exposeString"),
+        FieldType::Int => expose("exposeInt"),
+        FieldType::Float => expose("exposeFloat"),
+        FieldType::Boolean => expose("exposeBoolean"),
+        FieldType::DateTime => typed("\"DateTime\""),
+        FieldType::Json => typed("\"JSON\""),
+        FieldType::Decimal => typed("\"Decimal\""),
+        FieldType::BigInt => typed("\"BigInt\""),
+        FieldType::Bytes => typed("\"Bytes\""),
+        FieldType::Enum(enum_name) => typed(enum_name),
+        // This shouldn't happen for non-relation fields, but handle it
+        FieldType::Model(_) => expose("exposeString"),
     }
 }
@@ -1,117 +1,479 @@
-use crate::parser::ParsedSchema;
-use anyhow::Result;
+use crate::generator::get_prisma_name;
+use crate::generator::helpers::capitalize_first;
+use crate::generator::inputs::{
+    aggregate_order_by_inputs, create_input_fields, create_required, generate_input_field, input_options, json_null_field,
+    list_update_fields, list_update_input_name, push_list_update_imports, update_input_fields, update_operations_imports,
+};
+use crate::parser::{Field, FieldType, Model, ParsedSchema, Provider};
+use anyhow::{bail, Result};
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::fs;
 use std::path::Path;
 
+/// A relation field and the model on the other side
+struct RelationInput<'a> {
+    model: &'a Model,
+    field: &'a Field,
+    related: &'a Model,
+    /// The field of `related` on the other side of the relation
+    back: Option<&'a Field>,
+    /// Foreign keys of the related model pointing back to `model`; nested `createMany` needs them
+    /// and is only allowed on the list side of a one-to-many relation
+    back_keys: Option<&'a [String]>,
+}
+
+/// Input of a relation field in create inputs: `create`, `connectOrCreate`, `createMany` and `connect`
+pub fn create_relation_input_name(model: &Model, field: &Field) -> String {
+    relation_input_name(model, field, "Create")
+}
+
+/// Input of a relation field in update inputs, which can also `update`, `upsert`, `delete`...
+pub fn update_relation_input_name(model: &Model, field: &Field) -> String {
+    relation_input_name(model, field, "Update")
+}
+
+/// Inputs are named after the relation field, so several relations to the same model
+/// (`author` and `editor`) and self-relations each get their own, e.g. `PostCreateNestedAuthorInput`
+fn relation_input_name(model: &Model, field: &Field, side: &str) -> String {
+    format!("{}{}Nested{}Input", model.name, side, capitalize_first(&field.name))
+}
+
+/// `{Model}CreateMany{Field}Input`, the `createMany` of a list relation, and the input of its rows
+fn create_many_input_names(model: &Model, field: &Field) -> (String, String) {
+    let field_name = capitalize_first(&field.name);
+    (
+        format!("{}CreateMany{}Input", model.name, field_name),
+        format!("{}CreateMany{}DataInput", model.name, field_name),
+    )
+}
+
+/// Inputs of the related rows written through a relation field
+/// Prisma fills in the relation back to the parent itself and rejects it in nested writes, so when
+/// the other side exists they leave it out, e.g. `PostCreateWithoutAuthorInput` for `User.posts`.
+/// Without it, the model's own `{Related}CreateInput`/`{Related}UpdateInput` are used.
+struct NestedInputNames {
+    create: String,
+    update: String,
+    create_or_connect: String,
+    upsert: String,
+    upsert_with_where_unique: String,
+    update_with_where_unique: String,
+    update_many_with_where: String,
+}
+
+fn nested_input_names(relation: &RelationInput) -> NestedInputNames {
+    let related = &relation.related.name;
+    let without = relation
+        .back
+        .map(|back| format!("Without{}", capitalize_first(&back.name)))
+        .unwrap_or_default();
+    let name = |kind: &str| format!("{}{}{}Input", related, kind, without);
+    NestedInputNames {
+        create: name("Create"),
+        update: name("Update"),
+        create_or_connect: name("CreateOrConnect"),
+        upsert: name("Upsert"),
+        upsert_with_where_unique: name("UpsertWithWhereUnique"),
+        update_with_where_unique: name("UpdateWithWhereUnique"),
+        update_many_with_where: name("UpdateManyWithWhere"),
+    }
+}
+
+/// The nested inputs written for a relation field; relations to the same model without
+/// an other side share theirs
+fn generated_nested_input_names(relation: &RelationInput) -> Vec<String> {
+    let names = nested_input_names(relation);
+    let mut generated = Vec::new();
+    if relation.back.is_some() {
+        generated.extend([names.create, names.update]);
+    }
+    generated.push(names.create_or_connect);
+    if relation.field.is_list {
+        generated.extend([
+            names.upsert_with_where_unique,
+            names.update_with_where_unique,
+            names.update_many_with_where,
+        ]);
+    } else {
+        generated.push(names.upsert);
+    }
+    generated
+}
+
 /// Generate all relation input types for all models
-pub fn generate_all_relation_inputs(schema: &ParsedSchema, output_dir: &Path) -> Result<()> {
+/// With `update_operations`, the nested update inputs use `{Type}FieldUpdateOperationsInput` like `{Model}UpdateInput`
+pub fn generate_all_relation_inputs(schema: &ParsedSchema, output_dir: &Path, update_operations: bool) -> Result<()> {
     let inputs_dir = output_dir.join("inputs");
     fs::create_dir_all(&inputs_dir)?;
 
-    let mut all_relation_types = Vec::new();
+    // Generate a single relations.ts file with all relation input types
+    generate_relations_file(schema, &relation_inputs(schema), &inputs_dir, update_operations)?;
 
-    // Collect all relation input types needed
+    Ok(())
+}
+
+/// Fail when a relation input would get the name of another relation input or of another
+/// generated type, since GraphQL type names must be unique
+pub fn check_relation_inputs(schema: &ParsedSchema) -> Result<()> {
+    check_collisions(schema, &relation_inputs(schema))
+}
+
+/// Every relation field of the schema
+fn relation_inputs(schema: &ParsedSchema) -> Vec<RelationInput<'_>> {
+    let mut relations = Vec::new();
     for model in &schema.models {
         for field in &model.fields {
-            if let Some(_relation) = &field.relation {
-                if let crate::parser::FieldType::Model(related_model) = &field.field_type {
-                    let relation_input_name = if field.is_list {
-                        format!("{}{}ListRelationInput", model.name, related_model)
-                    } else {
-                        format!("{}{}RelationInput", model.name, related_model)
-                    };
-                    
-                    if !all_relation_types.iter().any(|(name, _, _, _)| name == &relation_input_name) {
-                        all_relation_types.push((
-                            relation_input_name,
-                            model.name.clone(),
-                            related_model.clone(),
-                            field.is_list,
-                        ));
-                    }
-                }
-            }
-        }
-    }
+            let (Some(relation), FieldType::Model(related_name)) = (&field.relation, &field.field_type) else {
+                continue;
+            };
+            let Some(related) = schema.models.iter().find(|m| &m.name == related_name) else {
+                continue;
+            };
 
-    // Generate a single relations.ts file with all relation input types
-    generate_relations_file(&all_relation_types, &inputs_dir)?;
+            // The field on the other side of the same relation
+            let back_relation = related.fields.iter().find(|f| {
+                !(related.name == model.name && f.name == field.name)
+                    && matches!(&f.field_type, FieldType::Model(m) if m == &model.name)
+                    && f.relation.as_ref().is_some_and(|r| r.name == relation.name)
+            });
+            let back_keys = back_relation
+                .and_then(|f| f.relation.as_ref())
+                .filter(|r| field.is_list && !r.fields.is_empty())
+                .map(|r| r.fields.as_slice());
 
-    Ok(())
+            relations.push(RelationInput {
+                model,
+                field,
+                related,
+                back: back_relation,
+                back_keys,
+            });
+        }
+    }
+    relations
 }
 
 fn generate_relations_file(
-    relation_types: &[(String, String, String, bool)],
+    schema: &ParsedSchema,
+    relations: &[RelationInput],
     dir: &Path,
+    update_operations: bool,
 ) -> Result<()> {
-    let mut content = String::new();
-
-    content.push_str("import { builder } from \"../builder\";\n");
-    
-    // Import all WhereUniqueRelationInput and RelationCreateInput types
-    let mut unique_models: Vec<String> = Vec::new();
-    for (_, _, related_model, _) in relation_types {
-        if !unique_models.contains(related_model) {
-            unique_models.push(related_model.clone());
-        }
-    }
-    
-    for model in &unique_models {
-        content.push_str(&format!(
-            "import {{ {}WhereUniqueRelationInput }} from \"./{}WhereUniqueRelationInput\";\n",
-            model, model
-        ));
-        content.push_str(&format!(
-            "import {{ {}RelationCreateInput }} from \"./{}RelationCreateInput\";\n",
-            model, model
-        ));
+    // Related models, whether a list relation points to them and whether one has no other side
+    let mut related_models: Vec<(&Model, bool, bool)> = Vec::new();
+    for relation in relations {
+        let without_back = relation.back.is_none();
+        match related_models.iter_mut().find(|(m, _, _)| m.name == relation.related.name) {
+            Some((_, list, fallback)) => {
+                *list |= relation.field.is_list;
+                *fallback |= without_back;
+            }
+            None => related_models.push((relation.related, relation.field.is_list, without_back)),
+        }
+    }
+    // Related models written through `{Related}CreateWithout{Field}Input`/`UpdateWithout`
+    let mut nested_models: Vec<&Model> = Vec::new();
+    for relation in relations.iter().filter(|r| r.back.is_some()) {
+        if !nested_models.iter().any(|m| m.name == relation.related.name) {
+            nested_models.push(relation.related);
+        }
     }
-    
-    content.push('\n');
 
-    // Generate each relation input type
-    for (input_name, _parent_model, related_model, is_list) in relation_types {
-        content.push_str(&format!(
-            "export const {} = builder.inputType(\"{}\", {{\n",
-            input_name, input_name
-        ));
-        content.push_str("  fields: (t) => ({\n");
-
-        if *is_list {
-            // For list relations: connect, create, disconnect
-            content.push_str(&format!(
-                "    connect: t.field({{ type: [{}WhereUniqueRelationInput] }}),\n",
-                related_model
-            ));
-            content.push_str(&format!(
-                "    create: t.field({{ type: [{}RelationCreateInput] }}),\n",
-                related_model
-            ));
-            content.push_str(&format!(
-                "    disconnect: t.field({{ type: [{}WhereUniqueRelationInput] }}),\n",
-                related_model
-            ));
-        } else {
-            // For single relations: connect, create, disconnect
-            content.push_str(&format!(
-                "    connect: t.field({{ type: {}WhereUniqueRelationInput }}),\n",
-                related_model
-            ));
-            content.push_str(&format!(
-                "    create: t.field({{ type: {}RelationCreateInput }}),\n",
-                related_model
-            ));
-            content.push_str(&format!(
-                "    disconnect: t.field({{ type: {}WhereUniqueRelationInput }}),\n",
-                related_model
-            ));
-        }
-
-        content.push_str("  }),\n");
-        content.push_str("});\n\n");
+    let mut content = String::from("import { builder } from \"../builder\";\n");
+
+    let nested_enums = nested_models
+        .iter()
+        .flat_map(|m| &m.fields)
+        .filter(|f| f.relation.is_none() && !(f.directives.omit_create && f.directives.omit_update));
+    let used_enums: BTreeSet<&str> = relations
+        .iter()
+        .flat_map(create_many_fields)
+        .chain(nested_enums)
+        .filter_map(|f| match &f.field_type {
+            FieldType::Enum(enum_name) => Some(enum_name.as_str()),
+            _ => json_null_field(f).map(|_| "NullableJsonNullValueInput"),
+        })
+        .collect();
+    if !used_enums.is_empty() {
+        let enum_imports: Vec<&str> = used_enums.into_iter().collect();
+        content.push_str(&format!("import {{ {} }} from \"../enums\";\n", enum_imports.join(", ")));
+    }
+
+    for (model, list, fallback) in &related_models {
+        let names = get_prisma_name(&model.name);
+        let mut imports = vec![format!("{}WhereUniqueRelationInput", model.name)];
+        if *fallback {
+            imports.push(names.create_input);
+            imports.push(names.update_input);
+        }
+        if *list {
+            imports.push(names.update_many_input);
+            imports.push(names.where_input);
+        }
+        for import in imports {
+            content.push_str(&format!("import {{ {} }} from \"./{}\";\n", import, import));
+        }
+    }
+    for model in &nested_models {
+        push_list_update_imports(&mut content, model);
+    }
+    if update_operations {
+        let operations: BTreeSet<String> = nested_models.iter().flat_map(|m| update_operations_imports(m)).collect();
+        if !operations.is_empty() {
+            let operations: Vec<String> = operations.into_iter().collect();
+            content.push_str(&format!("import {{ {} }} from \"./updateOperations\";\n", operations.join(", ")));
+        }
+    }
+
+    let skip_duplicates = schema.supports(Provider::supports_skip_duplicates);
+    let mut written = HashSet::new();
+    for relation in relations {
+        write_nested_inputs(&mut content, relation, update_operations, &mut written);
+        write_relation_inputs(&mut content, relation, skip_duplicates);
     }
 
     fs::write(dir.join("relations.ts"), content)?;
 
     Ok(())
 }
+
+/// Names already taken by the types of each model, then by each relation input
+fn check_collisions(schema: &ParsedSchema, relations: &[RelationInput]) -> Result<()> {
+    let mut taken: HashMap<String, String> = HashMap::new();
+    for model in &schema.models {
+        let names = get_prisma_name(&model.name);
+        let owner = format!("model `{}`", model.name);
+        let mut model_types = vec![
+            model.name.clone(),
+            names.create_input,
+            names.create_many_input,
+            names.update_input,
+            names.update_many_input,
+            names.where_input,
+            names.where_unique_input,
+            names.scalar_where_with_aggregates_input,
+            names.order_by_input,
+            names.order_by_with_aggregation_input,
+            format!("{}OrderByRelationAggregateInput", model.name),
+            format!("{}WhereUniqueRelationInput", model.name),
+            format!("{}RelationFilter", model.name),
+            format!("{}ListRelationFilter", model.name),
+        ];
+        model_types.extend(aggregate_order_by_inputs(model).into_iter().map(|(_, name, _)| name));
+        model_types.extend(list_update_fields(model).into_iter().map(|f| list_update_input_name(model, f)));
+        for type_name in model_types {
+            taken.insert(type_name, owner.clone());
+        }
+    }
+    for schema_enum in &schema.enums {
+        taken.insert(schema_enum.name.clone(), format!("enum `{}`", schema_enum.name));
+    }
+
+    let mut nested = HashSet::new();
+    for relation in relations {
+        let owner = format!("relation field `{}.{}`", relation.model.name, relation.field.name);
+        let mut type_names = vec![
+            create_relation_input_name(relation.model, relation.field),
+            update_relation_input_name(relation.model, relation.field),
+        ];
+        if relation.back_keys.is_some() {
+            let (create_many, data) = create_many_input_names(relation.model, relation.field);
+            type_names.extend([create_many, data]);
+        }
+        // Nested inputs shared with an earlier relation are already taken by it
+        type_names.extend(
+            generated_nested_input_names(relation)
+                .into_iter()
+                .filter(|name| nested.insert(name.clone())),
+        );
+        for type_name in type_names {
+            if let Some(other) = taken.insert(type_name.clone(), owner.clone()) {
+                bail!("input type `{}` of {} is also generated for {}", type_name, owner, other);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// The nested inputs of `generated_nested_input_names` that no earlier relation wrote
+fn write_nested_inputs(content: &mut String, relation: &RelationInput, operations: bool, written: &mut HashSet<String>) {
+    let names = nested_input_names(relation);
+    let related = relation.related;
+    let where_unique = format!("{}WhereUniqueRelationInput", related.name);
+
+    if relation.back.is_some() {
+        if written.insert(names.create.clone()) {
+            write_input_fields(content, &names.create, &create_input_fields(related, relation.back));
+        }
+        if written.insert(names.update.clone()) {
+            write_input_fields(content, &names.update, &update_input_fields(related, relation.back, operations));
+        }
+    }
+
+    let mut inputs = vec![(
+        &names.create_or_connect,
+        vec![("where", required(&where_unique)), ("create", required(&names.create))],
+    )];
+    if relation.field.is_list {
+        let related_names = get_prisma_name(&related.name);
+        inputs.extend([
+            (
+                &names.upsert_with_where_unique,
+                vec![
+                    ("where", required(&where_unique)),
+                    ("update", required(&names.update)),
+                    ("create", required(&names.create)),
+                ],
+            ),
+            (
+                &names.update_with_where_unique,
+                vec![("where", required(&where_unique)), ("data", required(&names.update))],
+            ),
+            (
+                &names.update_many_with_where,
+                vec![
+                    ("where", required(&related_names.where_input)),
+                    ("data", required(&related_names.update_many_input)),
+                ],
+            ),
+        ]);
+    } else {
+        inputs.push((
+            &names.upsert,
+            vec![("update", required(&names.update)), ("create", required(&names.create))],
+        ));
+    }
+
+    for (name, fields) in inputs {
+        if written.insert(name.clone()) {
+            write_input(content, name, &fields);
+        }
+    }
+}
+
+/// Create and update inputs of one relation field, with the operations Prisma allows for its cardinality
+fn write_relation_inputs(content: &mut String, relation: &RelationInput, skip_duplicates: bool) {
+    let related = &relation.related.name;
+    let nested = nested_input_names(relation);
+    let where_unique = format!("{}WhereUniqueRelationInput", related);
+    let create = &nested.create;
+    let connect_or_create = &nested.create_or_connect;
+    let (create_many_name, data_name) = create_many_input_names(relation.model, relation.field);
+    let create_many = relation.back_keys.map(|_| create_many_name);
+
+    // createMany takes the related rows without the foreign keys that point back to the parent
+    if let Some(create_many) = &create_many {
+        let data: Vec<String> = create_many_fields(relation)
+            .into_iter()
+            .flat_map(|f| {
+                let field = generate_input_field(&f.field_type, &f.name, f.is_list, &input_options(f, create_required(f)));
+                std::iter::once(field).chain(json_null_field(f))
+            })
+            .collect();
+        write_input_fields(content, &data_name, &data);
+
+        let mut fields = vec![("data", format!("t.field({{ type: [{}], required: true }})", data_name))];
+        if skip_duplicates {
+            fields.push(("skipDuplicates", "t.boolean()".to_string()));
+        }
+        write_input(content, create_many, &fields);
+    }
+
+    let create_name = create_relation_input_name(relation.model, relation.field);
+    let update_name = update_relation_input_name(relation.model, relation.field);
+
+    if relation.field.is_list {
+        let names = get_prisma_name(related);
+        let mut create_fields = vec![("create", list(create)), ("connectOrCreate", list(connect_or_create))];
+        if let Some(create_many) = &create_many {
+            create_fields.push(("createMany", single(create_many)));
+        }
+        create_fields.push(("connect", list(&where_unique)));
+        write_input(content, &create_name, &create_fields);
+
+        let mut update_fields = vec![
+            ("create", list(create)),
+            ("connectOrCreate", list(connect_or_create)),
+            ("upsert", list(&nested.upsert_with_where_unique)),
+        ];
+        if let Some(create_many) = &create_many {
+            update_fields.push(("createMany", single(create_many)));
+        }
+        update_fields.extend([
+            ("set", list(&where_unique)),
+            ("disconnect", list(&where_unique)),
+            ("delete", list(&where_unique)),
+            ("connect", list(&where_unique)),
+            ("update", list(&nested.update_with_where_unique)),
+            ("updateMany", list(&nested.update_many_with_where)),
+            ("deleteMany", list(&names.where_input)),
+        ]);
+        write_input(content, &update_name, &update_fields);
+    } else {
+        write_input(
+            content,
+            &create_name,
+            &[
+                ("create", single(create)),
+                ("connectOrCreate", single(connect_or_create)),
+                ("connect", single(&where_unique)),
+            ],
+        );
+
+        let mut update_fields = vec![
+            ("create", single(create)),
+            ("connectOrCreate", single(connect_or_create)),
+            ("upsert", single(&nested.upsert)),
+        ];
+        // Only an optional relation can be left empty
+        if !relation.field.is_required {
+            update_fields.push(("disconnect", "t.boolean()".to_string()));
+            update_fields.push(("delete", "t.boolean()".to_string()));
+        }
+        update_fields.push(("connect", single(&where_unique)));
+        update_fields.push(("update", single(&nested.update)));
+        write_input(content, &update_name, &update_fields);
+    }
+}
+
+/// Scalar fields of the related rows in a nested `createMany`
+fn create_many_fields<'a>(relation: &RelationInput<'a>) -> Vec<&'a Field> {
+    let Some(back_keys) = relation.back_keys else {
+        return Vec::new();
+    };
+    relation
+        .related
+        .fields
+        .iter()
+        .filter(|f| f.relation.is_none() && !f.directives.omit_create && !back_keys.contains(&f.name))
+        .collect()
+}
+
+fn single(type_name: &str) -> String {
+    format!("t.field({{ type: {} }})", type_name)
+}
+
+fn list(type_name: &str) -> String {
+    format!("t.field({{ type: [{}] }})", type_name)
+}
+
+fn required(type_name: &str) -> String {
+    format!("t.field({{ type: {}, required: true }})", type_name)
+}
+
+fn write_input(content: &mut String, name: &str, fields: &[(&str, String)]) {
+    let fields: Vec<String> = fields.iter().map(|(field_name, code)| format!("{}: {}", field_name, code)).collect();
+    write_input_fields(content, name, &fields);
+}
+
+fn write_input_fields(content: &mut String, name: &str, fields: &[String]) {
+    content.push_str(&format!(
+        "\nexport const {} = builder.inputType(\"{}\", {{\n  fields: (t) => ({{\n",
+        name, name
+    ));
+    for field in fields {
+        content.push_str(&format!("    {},\n", field));
+    }
+    content.push_str("  }),\n});\n");
+}
@@ -1,25 +1,36 @@
 use crate::parser::Model;
-use crate::generator::{get_prisma_name, helpers::capitalize_first};
+use crate::generator::{aggregates::aggregate_types, get_prisma_name, helpers::capitalize_first};
+use crate::generator::helpers::{json_nulls_import, prisma_arg};
 use anyhow::Result;
 use std::fs;
 use std::path::Path;
 
-pub fn generate(model: &Model, resolver_dir: &Path, _args_dir: &Path) -> Result<()> {
+pub fn generate(model: &Model, resolver_dir: &Path, _args_dir: &Path, json_nulls: bool) -> Result<()> {
     let names = get_prisma_name(&model.name);
     
     // Create aggregate query name: "aggregate" + capitalized model name (e.g., "aggregatePlace")
     let capitalized_model = capitalize_first(&model.name);
     let aggregate_name = format!("aggregate{}", capitalized_model);
 
+    // Only the aggregations with fields for this model
+    let aggregates = aggregate_types(model);
+    let type_imports: Vec<&str> = aggregates.iter().map(|(_, type_name)| type_name.as_str()).collect();
+    let result_fields: String = aggregates
+        .iter()
+        .map(|(key, type_name)| format!("    {}: t.field({{ type: {}, nullable: true }}),\n", key, type_name))
+        .collect();
+
     let content = format!(
-        r#"import {{ builder }} from "../builder";
+        r#"import {{ builder, aggregateSelection{json_nulls_import} }} from "../builder";
+import {{ {type_imports} }} from "../models/{model}Aggregates";
 import {{ {model}WhereInput }} from "../inputs/{model}WhereInput";
+import {{ {model}OrderByInput }} from "../inputs/{model}OrderByInput";
+import {{ {model}WhereUniqueInput }} from "../inputs/{model}WhereUniqueInput";
 
 // Define aggregate result type
 const {capitalized_model}AggregateResult = builder.simpleObject("Aggregate{capitalized_model}", {{
   fields: (t) => ({{
-    _count: t.int({{}}),
-  }}),
+{result_fields}  }}),
 }});
 
 builder.queryField("{aggregate_name}", (t) =>
@@ -28,20 +39,31 @@ builder.queryField("{aggregate_name}", (t) =>
     nullable: false,
     args: {{
       where: t.arg({{ type: {model}WhereInput }}),
+      orderBy: t.arg({{ type: [{model}OrderByInput] }}),
+      cursor: t.arg({{ type: {model}WhereUniqueInput }}),
+      take: t.arg.int(),
+      skip: t.arg.int(),
     }},
-    resolve: async (_root, args, ctx) => {{
+    resolve: async (_root, args, ctx, info) => {{
+      // Only the aggregations selected in the query are computed
       const result = await ctx.prisma.{prisma_model}.aggregate({{
-        where: args.where ?? undefined,
-        _count: true,
+        where: {where_arg} ?? undefined,
+        orderBy: (args.orderBy ?? undefined) as any,
+        cursor: args.cursor ?? undefined,
+        take: args.take ?? undefined,
+        skip: args.skip ?? undefined,
+        ...aggregateSelection(info),
       }});
-      return {{
-        _count: result._count,
-      }};
+      return result as any;
     }},
   }})
 );
 "#,
+        json_nulls_import = json_nulls_import(json_nulls),
+        where_arg = prisma_arg("where", json_nulls),
         model = model.name,
+        type_imports = type_imports.join(", "),
+        result_fields = result_fields,
         capitalized_model = capitalized_model,
         aggregate_name = aggregate_name,
         prisma_model = names.query_new2  // Use query_new2 for Prisma client calls
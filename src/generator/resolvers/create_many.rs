@@ -1,14 +1,25 @@
-use crate::parser::Model;
+use crate::parser::{Model, ParsedSchema, Provider};
 use crate::generator::get_prisma_name;
+use crate::generator::helpers::{json_nulls_import, prisma_arg};
 use anyhow::Result;
 use std::fs;
 use std::path::Path;
 
-pub fn generate(model: &Model, resolver_dir: &Path, _args_dir: &Path) -> Result<()> {
+pub fn generate(model: &Model, schema: &ParsedSchema, resolver_dir: &Path, _args_dir: &Path, json_nulls: bool) -> Result<()> {
     let names = get_prisma_name(&model.name);
 
+    // SQLite, SQL Server and MongoDB reject `skipDuplicates`
+    let (skip_duplicates_arg, skip_duplicates) = if schema.supports(Provider::supports_skip_duplicates) {
+        (
+            "\n      skipDuplicates: t.arg.boolean(),",
+            "\n        skipDuplicates: args.skipDuplicates ?? undefined,",
+        )
+    } else {
+        ("", "")
+    };
+
     let content = format!(
-        r#"import {{ builder, AffectedRowsOutput }} from "../builder";
+        r#"import {{ builder, AffectedRowsOutput{json_nulls_import} }} from "../builder";
 import {{ {create_many_input} }} from "../inputs/{create_many_input}";
 
 builder.mutationField("{mutation_name}", (t) =>
@@ -16,22 +27,24 @@ builder.mutationField("{mutation_name}", (t) =>
     type: AffectedRowsOutput,
     nullable: false,
     args: {{
-      data: t.arg({{ type: [{create_many_input}], required: true }}),
-      skipDuplicates: t.arg.boolean(),
+      data: t.arg({{ type: [{create_many_input}], required: true }}),{skip_duplicates_arg}
     }},
     resolve: async (_root, args, ctx) => {{
       const result = await ctx.prisma.{prisma_model}.createMany({{
-        data: args.data,
-        skipDuplicates: args.skipDuplicates ?? undefined,
+        data: {data_arg},{skip_duplicates}
       }});
       return result;
     }},
   }})
 );
 "#,
+        json_nulls_import = json_nulls_import(json_nulls),
+        data_arg = prisma_arg("data", json_nulls),
         create_many_input = names.create_many_input,
         prisma_model = names.query_new2,
-        mutation_name = names.create_many
+        mutation_name = names.create_many,
+        skip_duplicates_arg = skip_duplicates_arg,
+        skip_duplicates = skip_duplicates
     );
 
     fs::write(
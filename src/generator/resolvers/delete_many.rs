@@ -0,0 +1,44 @@
+use crate::parser::Model;
+use crate::generator::get_prisma_name;
+use crate::generator::helpers::{json_nulls_import, prisma_arg};
+use anyhow::Result;
+use std::fs;
+use std::path::Path;
+
+pub fn generate(model: &Model, resolver_dir: &Path, _args_dir: &Path, json_nulls: bool) -> Result<()> {
+    let names = get_prisma_name(&model.name);
+
+    let content = format!(
+        r#"import {{ builder, AffectedRowsOutput{json_nulls_import} }} from "../builder";
+import {{ {where_input} }} from "../inputs/{where_input}";
+
+builder.mutationField("{mutation_name}", (t) =>
+  t.field({{
+    type: AffectedRowsOutput,
+    nullable: false,
+    args: {{
+      where: t.arg({{ type: {where_input} }}),
+    }},
+    resolve: async (_root, args, ctx) => {{
+      const result = await ctx.prisma.{prisma_model}.deleteMany({{
+        where: {where_arg} ?? undefined,
+      }});
+      return result;
+    }},
+  }})
+);
+"#,
+        json_nulls_import = json_nulls_import(json_nulls),
+        where_arg = prisma_arg("where", json_nulls),
+        where_input = names.where_input,
+        prisma_model = names.query_new2,
+        mutation_name = names.delete_many
+    );
+
+    fs::write(
+        resolver_dir.join(format!("deleteMany{}.ts", model.name)),
+        content,
+    )?;
+
+    Ok(())
+}
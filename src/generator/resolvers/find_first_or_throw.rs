@@ -0,0 +1,59 @@
+use crate::parser::Model;
+use crate::generator::get_prisma_name;
+use crate::generator::helpers::{json_nulls_import, prisma_arg};
+use anyhow::Result;
+use std::fs;
+use std::path::Path;
+
+pub fn generate(model: &Model, resolver_dir: &Path, _args_dir: &Path, json_nulls: bool) -> Result<()> {
+    let names = get_prisma_name(&model.name);
+
+    let content = format!(
+        r#"import {{ builder, rethrowNotFound{json_nulls_import} }} from "../builder";
+import {{ {model} }} from "../models/{model}";
+import {{ {model}WhereInput }} from "../inputs/{model}WhereInput";
+import {{ {model}OrderByInput }} from "../inputs/{model}OrderByInput";
+import {{ {model}WhereUniqueInput }} from "../inputs/{model}WhereUniqueInput";
+import {{ {scalar_field_enum} }} from "../enums";
+
+builder.queryField("{query_name}", (t) =>
+  t.prismaField({{
+    type: "{model}",
+    nullable: false,
+    args: {{
+      where: t.arg({{ type: {model}WhereInput }}),
+      orderBy: t.arg({{ type: [{model}OrderByInput] }}),
+      cursor: t.arg({{ type: {model}WhereUniqueInput }}),
+      skip: t.arg.int(),
+      take: t.arg.int(),
+      distinct: t.arg({{ type: [{scalar_field_enum}] }}),
+    }},
+    resolve: async (query, _root, args, ctx) => {{
+      return ctx.prisma.{prisma_model}.findFirstOrThrow({{
+        ...query,
+        where: {where_arg} ?? undefined,
+        orderBy: (args.orderBy ?? undefined) as any,
+        cursor: args.cursor ?? undefined,
+        skip: args.skip ?? undefined,
+        take: args.take ?? undefined,
+        distinct: args.distinct ?? undefined,
+      }}).catch(rethrowNotFound("{model}"));
+    }},
+  }})
+);
+"#,
+        json_nulls_import = json_nulls_import(json_nulls),
+        where_arg = prisma_arg("where", json_nulls),
+        model = model.name,
+        prisma_model = names.query_new2,  // Use query_new2 for Prisma client calls
+        query_name = names.find_first_or_throw,
+        scalar_field_enum = names.scalar_field_enum
+    );
+
+    fs::write(
+        resolver_dir.join(format!("findFirstOrThrow{}.ts", model.name)),
+        content,
+    )?;
+
+    Ok(())
+}
@@ -1,17 +1,36 @@
 use crate::parser::Model;
-use crate::generator::get_prisma_name;
+use crate::generator::{get_prisma_name, helpers::relay_cursor};
+use crate::generator::helpers::{json_nulls_import, prisma_arg};
 use anyhow::Result;
 use std::fs;
 use std::path::Path;
 
-pub fn generate(model: &Model, resolver_dir: &Path, _args_dir: &Path) -> Result<()> {
+pub fn generate(model: &Model, resolver_dir: &Path, _args_dir: &Path, relay: bool, json_nulls: bool) -> Result<()> {
+    // Relay connections need a unique field or key to build cursors from
+    let content = match relay_cursor(model).filter(|_| relay) {
+        Some(cursor) => connection(model, &cursor, json_nulls),
+        None => list(model, json_nulls),
+    };
+
+    fs::write(
+        resolver_dir.join(format!("findMany{}.ts", model.name)),
+        content,
+    )?;
+
+    Ok(())
+}
+
+/// `findMany` returning a plain list
+fn list(model: &Model, json_nulls: bool) -> String {
     let names = get_prisma_name(&model.name);
 
-    let content = format!(
-        r#"import {{ builder }} from "../builder";
+    format!(
+        r#"import {{ builder{json_nulls_import} }} from "../builder";
 import {{ {model} }} from "../models/{model}";
 import {{ {model}WhereInput }} from "../inputs/{model}WhereInput";
 import {{ {model}OrderByInput }} from "../inputs/{model}OrderByInput";
+import {{ {model}WhereUniqueInput }} from "../inputs/{model}WhereUniqueInput";
+import {{ {scalar_field_enum} }} from "../enums";
 
 builder.queryField("{query_name}", (t) =>
   t.prismaField({{
@@ -20,30 +39,70 @@ builder.queryField("{query_name}", (t) =>
     args: {{
       where: t.arg({{ type: {model}WhereInput }}),
       orderBy: t.arg({{ type: [{model}OrderByInput] }}),
-      first: t.arg.int(),
-      last: t.arg.int(),
+      cursor: t.arg({{ type: {model}WhereUniqueInput }}),
+      take: t.arg.int(),
+      skip: t.arg.int(),
+      distinct: t.arg({{ type: [{scalar_field_enum}] }}),
     }},
     resolve: async (query, _root, args, ctx) => {{
       return ctx.prisma.{prisma_model}.findMany({{
         ...query,
-        where: args.where ?? undefined,
+        where: {where_arg} ?? undefined,
         orderBy: (args.orderBy ?? undefined) as any,
-        take: args.first ?? undefined,
-        skip: args.last ?? undefined,
+        cursor: args.cursor ?? undefined,
+        take: args.take ?? undefined,
+        skip: args.skip ?? undefined,
+        distinct: args.distinct ?? undefined,
       }});
     }},
   }})
 );
 "#,
+        json_nulls_import = json_nulls_import(json_nulls),
+        where_arg = prisma_arg("where", json_nulls),
         model = model.name,
         prisma_model = names.query_new2,  // Use query_new2 for Prisma client calls
-        query_name = names.find_many     // Use find_many for GraphQL field name (camelCase + plural)
-    );
+        query_name = names.find_many,     // Use find_many for GraphQL field name (camelCase + plural)
+        scalar_field_enum = names.scalar_field_enum
+    )
+}
 
-    fs::write(
-        resolver_dir.join(format!("findMany{}.ts", model.name)),
-        content,
-    )?;
+/// `findMany` as a Relay connection; `first`, `last`, `before` and `after` come from the plugin
+fn connection(model: &Model, cursor: &str, json_nulls: bool) -> String {
+    let names = get_prisma_name(&model.name);
 
-    Ok(())
+    format!(
+        r#"import {{ builder{json_nulls_import} }} from "../builder";
+import {{ {model} }} from "../models/{model}";
+import {{ {model}WhereInput }} from "../inputs/{model}WhereInput";
+import {{ {model}OrderByInput }} from "../inputs/{model}OrderByInput";
+
+builder.queryField("{query_name}", (t) =>
+  t.prismaConnection({{
+    type: "{model}",
+    cursor: "{cursor}",
+    nullable: false,
+    args: {{
+      where: t.arg({{ type: {model}WhereInput }}),
+      orderBy: t.arg({{ type: [{model}OrderByInput] }}),
+    }},
+    totalCount: (_parent, args, ctx) =>
+      ctx.prisma.{prisma_model}.count({{ where: {where_arg} ?? undefined }}),
+    resolve: async (query, _root, args, ctx) => {{
+      return ctx.prisma.{prisma_model}.findMany({{
+        ...query,
+        where: {where_arg} ?? undefined,
+        orderBy: (args.orderBy ?? undefined) as any,
+      }});
+    }},
+  }})
+);
+"#,
+        json_nulls_import = json_nulls_import(json_nulls),
+        where_arg = prisma_arg("where", json_nulls),
+        model = model.name,
+        cursor = cursor,
+        prisma_model = names.query_new2,  // Use query_new2 for Prisma client calls
+        query_name = names.find_many
+    )
 }
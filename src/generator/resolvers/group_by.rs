@@ -0,0 +1,66 @@
+use crate::parser::Model;
+use crate::generator::get_prisma_name;
+use crate::generator::helpers::{json_nulls_import, prisma_arg};
+use anyhow::Result;
+use std::fs;
+use std::path::Path;
+
+pub fn generate(model: &Model, resolver_dir: &Path, _args_dir: &Path, json_nulls: bool) -> Result<()> {
+    let names = get_prisma_name(&model.name);
+
+    let content = format!(
+        r#"import {{ builder, aggregateSelection{json_nulls_import} }} from "../builder";
+import {{ {group_by_output} }} from "../models/{model}Aggregates";
+import {{ {model}WhereInput }} from "../inputs/{model}WhereInput";
+import {{ {order_by_input} }} from "../inputs/{order_by_input}";
+import {{ {having_input} }} from "../inputs/{having_input}";
+import {{ {scalar_field_enum} }} from "../enums";
+
+builder.queryField("{query_name}", (t) =>
+  t.field({{
+    type: [{group_by_output}],
+    nullable: false,
+    args: {{
+      by: t.arg({{ type: [{scalar_field_enum}], required: true }}),
+      where: t.arg({{ type: {model}WhereInput }}),
+      having: t.arg({{ type: {having_input} }}),
+      orderBy: t.arg({{ type: [{order_by_input}] }}),
+      take: t.arg.int(),
+      skip: t.arg.int(),
+    }},
+    resolve: async (_root, args, ctx, info) => {{
+      // Only the aggregations selected in the query are computed
+      const groups = await ctx.prisma.{prisma_model}.groupBy({{
+        by: args.by,
+        where: {where_arg} ?? undefined,
+        having: {having_arg} ?? undefined,
+        orderBy: (args.orderBy ?? undefined) as any,
+        take: args.take ?? undefined,
+        skip: args.skip ?? undefined,
+        ...aggregateSelection(info),
+      }} as any);
+      return groups as any;
+    }},
+  }})
+);
+"#,
+        json_nulls_import = json_nulls_import(json_nulls),
+        where_arg = prisma_arg("where", json_nulls),
+        having_arg = prisma_arg("having", json_nulls),
+        model = model.name,
+        group_by_output = names.group_by_output,
+        having_input = names.scalar_where_with_aggregates_input,
+        // Groups are sorted by their columns and aggregates, not by relations
+        order_by_input = names.order_by_with_aggregation_input,
+        scalar_field_enum = names.scalar_field_enum,
+        prisma_model = names.query_new2,  // Use query_new2 for Prisma client calls
+        query_name = names.group_by
+    );
+
+    fs::write(
+        resolver_dir.join(format!("groupBy{}.ts", model.name)),
+        content,
+    )?;
+
+    Ok(())
+}
@@ -2,11 +2,22 @@ mod create_one;
 mod create_many;
 mod find_many;
 mod find_unique;
+mod find_first;
+mod find_first_or_throw;
+mod find_unique_or_throw;
 mod aggregate;
+mod group_by;
 mod update_one;
+mod update_many;
+mod upsert_one;
+mod delete_one;
+mod delete_many;
 mod relations;
 
+use crate::config::{Config, Operation};
+use crate::generator::{get_prisma_name, helpers::{capitalize_first, has_json_nulls}};
 use crate::parser::{Model, ParsedSchema};
+use crate::scanner::ManualResolvers;
 use anyhow::Result;
 use std::fs;
 use std::path::Path;
@@ -16,57 +27,49 @@ pub fn generate_resolvers(
     model: &Model, 
     schema: &ParsedSchema, 
     output_dir: &Path,
-    manual_resolvers: &crate::scanner::ManualResolvers,
-    verbose: bool,
+    manual_resolvers: &ManualResolvers,
+    config: &Config,
 ) -> Result<()> {
-    use crate::generator::get_prisma_name;
-    
     // Create directories
     let resolvers_dir = output_dir.join("resolvers");
     fs::create_dir_all(&resolvers_dir)?;
 
-    let names = get_prisma_name(&model.name);
+    let json_nulls = has_json_nulls(schema);
 
     // Generate CRUD resolvers (in single directory for Pothos)
-    // Check if each resolver exists manually before generating
-    // Note: createOne and updateOne are mutations, others are queries
-    
-    if !manual_resolvers.contains_mutation(&names.create) {
-        create_one::generate(model, &resolvers_dir, &resolvers_dir)?;
-    } else if verbose {
-        println!("  ⏭️  Skipping createOne{} (manual mutation found: {})", model.name, names.create);
-    }
-    
-    if !manual_resolvers.contains_mutation(&names.create_many) {
-        create_many::generate(model, &resolvers_dir, &resolvers_dir)?;
-    } else if verbose {
-        println!("  ⏭️  Skipping createMany{} (manual mutation found: {})", model.name, names.create_many);
-    }
-    
-    if !manual_resolvers.contains_query(&names.find_many) {
-        find_many::generate(model, &resolvers_dir, &resolvers_dir)?;
-    } else if verbose {
-        println!("  ⏭️  Skipping findMany{} (manual query found: {})", model.name, names.find_many);
-    }
-    
-    if !manual_resolvers.contains_query(&names.find) {
-        find_unique::generate(model, &resolvers_dir, &resolvers_dir)?;
-    } else if verbose {
-        println!("  ⏭️  Skipping findUnique{} (manual query found: {})", model.name, names.find);
-    }
-    
-    // Aggregate uses a different naming pattern
-    let aggregate_name = format!("aggregate{}", model.name);
-    if !manual_resolvers.contains_query(&aggregate_name) {
-        aggregate::generate(model, &resolvers_dir, &resolvers_dir)?;
-    } else if verbose {
-        println!("  ⏭️  Skipping aggregate{} (manual query found: {})", model.name, aggregate_name);
-    }
-    
-    if !manual_resolvers.contains_mutation(&names.update) {
-        update_one::generate(model, &resolvers_dir, &resolvers_dir)?;
-    } else if verbose {
-        println!("  ⏭️  Skipping updateOne{} (manual mutation found: {})", model.name, names.update);
+    let enabled = enabled_operations(model, config, manual_resolvers);
+
+    for operation in Operation::ALL {
+        if !enabled.contains(&operation) {
+            if config.verbose {
+                let field_name = operation_field_name(model, operation);
+                if config.operation_enabled(&model.name, operation) {
+                    let kind = if operation.is_query() { "query" } else { "mutation" };
+                    println!("  ⏭️  Skipping {}{} (manual {} found: {})", operation.name(), model.name, kind, field_name);
+                } else {
+                    println!("  ⏭️  Skipping {}{} (disabled in config)", operation.name(), model.name);
+                }
+            }
+            continue;
+        }
+
+        match operation {
+            Operation::CreateOne => create_one::generate(model, &resolvers_dir, &resolvers_dir, json_nulls)?,
+            Operation::CreateMany => create_many::generate(model, schema, &resolvers_dir, &resolvers_dir, json_nulls)?,
+            Operation::FindMany => find_many::generate(model, &resolvers_dir, &resolvers_dir, config.relay, json_nulls)?,
+            Operation::FindUnique => find_unique::generate(model, &resolvers_dir, &resolvers_dir)?,
+            Operation::FindFirst => find_first::generate(model, &resolvers_dir, &resolvers_dir, json_nulls)?,
+            Operation::FindFirstOrThrow => find_first_or_throw::generate(model, &resolvers_dir, &resolvers_dir, json_nulls)?,
+            Operation::FindUniqueOrThrow => find_unique_or_throw::generate(model, &resolvers_dir, &resolvers_dir)?,
+            Operation::Aggregate => aggregate::generate(model, &resolvers_dir, &resolvers_dir, json_nulls)?,
+            Operation::GroupBy => group_by::generate(model, &resolvers_dir, &resolvers_dir, json_nulls)?,
+            Operation::UpdateOne => update_one::generate(model, &resolvers_dir, &resolvers_dir, json_nulls)?,
+            Operation::UpdateMany => update_many::generate(model, &resolvers_dir, &resolvers_dir, json_nulls)?,
+            Operation::UpsertOne => upsert_one::generate(model, &resolvers_dir, &resolvers_dir, json_nulls)?,
+            Operation::DeleteOne => delete_one::generate(model, &resolvers_dir, &resolvers_dir)?,
+            Operation::DeleteMany => delete_many::generate(model, &resolvers_dir, &resolvers_dir, json_nulls)?,
+            Operation::Queries | Operation::Mutations => {}
+        }
     }
 
     // Generate relations resolver (always generate, as it's model-specific)
@@ -74,3 +77,41 @@ pub fn generate_resolvers(
 
     Ok(())
 }
+
+/// Operations generated for a model: enabled in the config and not already written by hand
+pub fn enabled_operations(model: &Model, config: &Config, manual_resolvers: &ManualResolvers) -> Vec<Operation> {
+    Operation::ALL
+        .into_iter()
+        .filter(|operation| config.operation_enabled(&model.name, *operation))
+        .filter(|operation| {
+            let field_name = operation_field_name(model, *operation);
+            if operation.is_query() {
+                !manual_resolvers.contains_query(&field_name)
+            } else {
+                !manual_resolvers.contains_mutation(&field_name)
+            }
+        })
+        .collect()
+}
+
+/// Name of the Query or Mutation field generated for an operation
+fn operation_field_name(model: &Model, operation: Operation) -> String {
+    let names = get_prisma_name(&model.name);
+    match operation {
+        Operation::CreateOne => names.create,
+        Operation::CreateMany => names.create_many,
+        Operation::FindMany => names.find_many,
+        Operation::FindUnique => names.find,
+        Operation::FindFirst => names.find_first,
+        Operation::FindFirstOrThrow => names.find_first_or_throw,
+        Operation::FindUniqueOrThrow => names.find_unique_or_throw,
+        Operation::Aggregate => format!("aggregate{}", capitalize_first(&model.name)),
+        Operation::GroupBy => names.group_by,
+        Operation::UpdateOne => names.update,
+        Operation::UpdateMany => names.update_many,
+        Operation::UpsertOne => names.upsert,
+        Operation::DeleteOne => names.delete,
+        Operation::DeleteMany => names.delete_many,
+        Operation::Queries | Operation::Mutations => String::new(),
+    }
+}
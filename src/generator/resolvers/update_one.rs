@@ -1,14 +1,15 @@
 use crate::parser::Model;
 use crate::generator::get_prisma_name;
+use crate::generator::helpers::{json_nulls_import, prisma_arg};
 use anyhow::Result;
 use std::fs;
 use std::path::Path;
 
-pub fn generate(model: &Model, resolver_dir: &Path, _args_dir: &Path) -> Result<()> {
+pub fn generate(model: &Model, resolver_dir: &Path, _args_dir: &Path, json_nulls: bool) -> Result<()> {
     let names = get_prisma_name(&model.name);
 
     let content = format!(
-        r#"import {{ builder }} from "../builder";
+        r#"import {{ builder{json_nulls_import} }} from "../builder";
 import {{ {model} }} from "../models/{model}";
 import {{ {model}UpdateInput }} from "../inputs/{model}UpdateInput";
 import {{ {model}WhereUniqueInput }} from "../inputs/{model}WhereUniqueInput";
@@ -25,12 +26,14 @@ builder.mutationField("{mutation_name}", (t) =>
       return ctx.prisma.{prisma_model}.update({{
         ...query,
         where: args.where,
-        data: args.data,
+        data: {data_arg},
       }});
     }},
   }})
 );
 "#,
+        json_nulls_import = json_nulls_import(json_nulls),
+        data_arg = prisma_arg("data", json_nulls),
         model = model.name,
         prisma_model = names.query_new2,  // Use query_new2 for Prisma client calls
         mutation_name = names.update
@@ -0,0 +1,52 @@
+use crate::parser::Model;
+use crate::generator::get_prisma_name;
+use crate::generator::helpers::{json_nulls_import, prisma_arg};
+use anyhow::Result;
+use std::fs;
+use std::path::Path;
+
+pub fn generate(model: &Model, resolver_dir: &Path, _args_dir: &Path, json_nulls: bool) -> Result<()> {
+    let names = get_prisma_name(&model.name);
+
+    let content = format!(
+        r#"import {{ builder{json_nulls_import} }} from "../builder";
+import {{ {model} }} from "../models/{model}";
+import {{ {model}WhereUniqueInput }} from "../inputs/{model}WhereUniqueInput";
+import {{ {model}CreateInput }} from "../inputs/{model}CreateInput";
+import {{ {model}UpdateInput }} from "../inputs/{model}UpdateInput";
+
+builder.mutationField("{mutation_name}", (t) =>
+  t.prismaField({{
+    type: "{model}",
+    nullable: false,
+    args: {{
+      where: t.arg({{ type: {model}WhereUniqueInput, required: true }}),
+      create: t.arg({{ type: {model}CreateInput, required: true }}),
+      update: t.arg({{ type: {model}UpdateInput, required: true }}),
+    }},
+    resolve: async (query, _root, args, ctx) => {{
+      return ctx.prisma.{prisma_model}.upsert({{
+        ...query,
+        where: args.where,
+        create: {create_arg},
+        update: {update_arg},
+      }});
+    }},
+  }})
+);
+"#,
+        json_nulls_import = json_nulls_import(json_nulls),
+        create_arg = prisma_arg("create", json_nulls),
+        update_arg = prisma_arg("update", json_nulls),
+        model = model.name,
+        prisma_model = names.query_new2,  // Use query_new2 for Prisma client calls
+        mutation_name = names.upsert
+    );
+
+    fs::write(
+        resolver_dir.join(format!("upsertOne{}.ts", model.name)),
+        content,
+    )?;
+
+    Ok(())
+}
@@ -8,12 +8,13 @@ use std::io::ErrorKind;
 use clap::Parser as ClapParser;
 use std::path::PathBuf;
 use anyhow::Result;
+use parser::diagnostics::Diagnostics;
 
 #[derive(ClapParser, Debug)]
 #[command(name = "prisma-pothos-generator")]
 #[command(about = "Generate Pothos GraphQL code from Prisma schema")]
 struct Args {
-    /// Path to the Prisma schema file
+    /// Path to the Prisma schema file, a directory of .prisma files or a glob
     #[arg(short, long, default_value = "./prisma/schema.prisma")]
     schema: PathBuf,
 
@@ -38,8 +39,30 @@ fn main() -> Result<()> {
         println!("Output directory: {:?}", args.output);
 
         // Load configuration from .gpothosrc.json
-        let config = config::Config::load()?;
-        
+        let mut config = config::Config::load()?;
+
+        let sources = parser::load_schema_files(&args.schema)?;
+        if config.verbose {
+            for file in sources.files() {
+                println!("  📄 Schema file: {}", file.name);
+            }
+        }
+
+        let mut diagnostics = Diagnostics::new();
+        let parsed = parser::parse_schema(&sources, &mut diagnostics);
+
+        if !diagnostics.is_empty() {
+            eprintln!("{}", diagnostics.render(&sources));
+        }
+        let Some(parsed) = parsed else {
+            eprintln!("error: could not parse schema due to {} previous error(s)", diagnostics.error_count());
+            std::process::exit(1);
+        };
+
+        // Options from the schema's generator block override .gpothosrc.json
+        config.merge_schema_generator(&parsed)?;
+        config.check_models(&parsed)?;
+
         // Scan for manual resolvers if enabled
         let manual_resolvers = if config.auto_scan {
             if config.verbose {
@@ -56,9 +79,6 @@ fn main() -> Result<()> {
             scanner::ManualResolvers::new()
         };
 
-        let schema_content = std::fs::read_to_string(&args.schema)?;
-        let parsed = parser::parse_schema(&schema_content)?;
-
         let ruta_str: &str = args.output.as_path().to_str().expect("¡La ruta no es válida UTF-8!");
 
         // Intentar eliminar el directorio de forma recursiva
@@ -75,7 +95,7 @@ fn main() -> Result<()> {
             }
         }
 
-        generator::generate(&parsed, &args.output, &manual_resolvers, config.verbose)?;
+        generator::generate(&parsed, &args.output, &manual_resolvers, &config)?;
 
         println!("Generation complete!");
     }
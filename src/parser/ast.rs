@@ -0,0 +1,204 @@
+use super::diagnostics::Span;
+use std::fmt;
+
+/// Syntax tree of a single Prisma schema file
+#[derive(Debug, Clone, Default)]
+pub struct SchemaAst {
+    pub tops: Vec<Top>,
+}
+
+/// A top-level declaration
+#[derive(Debug, Clone)]
+pub enum Top {
+    Model(ModelBlock),
+    Enum(EnumBlock),
+    Datasource(ConfigBlock),
+    Generator(ConfigBlock),
+}
+
+#[derive(Debug, Clone)]
+pub struct Ident {
+    pub name: String,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone)]
+pub struct Comment {
+    pub text: String,
+    /// `///` comments document the declaration that follows them
+    pub is_doc: bool,
+    pub span: Span,
+}
+
+/// Which keyword introduced a model-like block
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModelKind {
+    Model,
+    View,
+    Type,
+}
+
+/// `model`, `view` or composite `type` block
+#[derive(Debug, Clone)]
+pub struct ModelBlock {
+    pub kind: ModelKind,
+    pub name: Ident,
+    pub fields: Vec<FieldDecl>,
+    /// `@@id`, `@@unique`, `@@index`, `@@map`...
+    pub attributes: Vec<Attribute>,
+    pub comments: Vec<Comment>,
+}
+
+#[derive(Debug, Clone)]
+pub struct FieldDecl {
+    pub name: Ident,
+    pub field_type: TypeRef,
+    pub attributes: Vec<Attribute>,
+    pub comments: Vec<Comment>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Arity {
+    Required,
+    Optional,
+    List,
+}
+
+#[derive(Debug, Clone)]
+pub struct TypeRef {
+    pub name: Ident,
+    pub arity: Arity,
+    /// Set for `Unsupported("...")` types
+    pub unsupported: Option<String>,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone)]
+pub struct EnumBlock {
+    pub name: Ident,
+    pub values: Vec<EnumValueDecl>,
+    pub attributes: Vec<Attribute>,
+}
+
+#[derive(Debug, Clone)]
+pub struct EnumValueDecl {
+    pub name: Ident,
+    pub attributes: Vec<Attribute>,
+    pub comments: Vec<Comment>,
+}
+
+/// `datasource` or `generator` block made of `key = value` properties
+#[derive(Debug, Clone)]
+pub struct ConfigBlock {
+    pub name: Ident,
+    pub properties: Vec<Property>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Property {
+    pub name: Ident,
+    pub value: Expression,
+    pub comments: Vec<Comment>,
+}
+
+/// Field attribute (`@id`) or block attribute (`@@unique([a, b])`)
+/// Native type attributes keep their full dotted name, e.g. `db.VarChar`
+#[derive(Debug, Clone)]
+pub struct Attribute {
+    pub name: Ident,
+    pub arguments: Vec<Argument>,
+    pub span: Span,
+}
+
+impl Attribute {
+    /// Look up an argument by name, falling back to the first positional argument
+    /// when `positional_fallback` is set (e.g. `@relation("name")` vs `@relation(name: "name")`)
+    pub fn argument(&self, name: &str, positional_fallback: bool) -> Option<&Argument> {
+        self.arguments
+            .iter()
+            .find(|a| a.name.as_ref().is_some_and(|n| n.name == name))
+            .or_else(|| {
+                if positional_fallback {
+                    self.arguments.iter().find(|a| a.name.is_none())
+                } else {
+                    None
+                }
+            })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Argument {
+    pub name: Option<Ident>,
+    pub value: Expression,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone)]
+pub enum Expression {
+    String(String, Span),
+    Number(String, Span),
+    /// Bare identifier such as `Cascade`, `true` or a field name
+    Constant(String, Span),
+    Array(Vec<Expression>, Span),
+    Function(String, Vec<Argument>, Span),
+}
+
+impl Expression {
+    pub fn span(&self) -> Span {
+        match self {
+            Expression::String(_, span)
+            | Expression::Number(_, span)
+            | Expression::Constant(_, span)
+            | Expression::Array(_, span)
+            | Expression::Function(_, _, span) => *span,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Expression::String(value, _) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn as_constant(&self) -> Option<&str> {
+        match self {
+            Expression::Constant(value, _) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&[Expression]> {
+        match self {
+            Expression::Array(items, _) => Some(items),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Expression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Expression::String(value, _) => write!(f, "{:?}", value),
+            Expression::Number(value, _) | Expression::Constant(value, _) => write!(f, "{}", value),
+            Expression::Array(items, _) => {
+                let items: Vec<String> = items.iter().map(|i| i.to_string()).collect();
+                write!(f, "[{}]", items.join(", "))
+            }
+            Expression::Function(name, args, _) => {
+                let args: Vec<String> = args.iter().map(|a| a.to_string()).collect();
+                write!(f, "{}({})", name, args.join(", "))
+            }
+        }
+    }
+}
+
+impl fmt::Display for Argument {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.name {
+            Some(name) => write!(f, "{}: {}", name.name, self.value),
+            None => write!(f, "{}", self.value),
+        }
+    }
+}
@@ -0,0 +1,242 @@
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+use std::path::Path;
+
+/// Index of a file inside a `SourceMap`
+pub type FileId = usize;
+
+/// Location of a token or declaration: a byte range inside one source file
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Span {
+    pub file: FileId,
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Span {
+    pub fn new(file: FileId, start: usize, end: usize) -> Self {
+        Self { file, start, end }
+    }
+
+    /// Smallest span covering both `self` and `other` (which must be in the same file)
+    pub fn to(self, other: Span) -> Span {
+        Span::new(self.file, self.start.min(other.start), self.end.max(other.end))
+    }
+}
+
+#[derive(Debug)]
+pub struct SourceFile {
+    pub name: String,
+    pub content: String,
+}
+
+/// All schema files taking part in a generation run
+#[derive(Debug, Default)]
+pub struct SourceMap {
+    files: Vec<SourceFile>,
+}
+
+impl SourceMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add(&mut self, name: impl Into<String>, content: impl Into<String>) -> FileId {
+        self.files.push(SourceFile {
+            name: name.into(),
+            content: content.into(),
+        });
+        self.files.len() - 1
+    }
+
+    /// Read a schema file from disk and add it to the map
+    pub fn load(&mut self, path: &Path) -> std::io::Result<FileId> {
+        let content = std::fs::read_to_string(path)?;
+        Ok(self.add(path.display().to_string(), content))
+    }
+
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    pub fn files(&self) -> impl Iterator<Item = &SourceFile> {
+        self.files.iter()
+    }
+
+    pub fn file(&self, id: FileId) -> &SourceFile {
+        &self.files[id]
+    }
+
+    /// 1-based line and column of a byte offset
+    pub fn line_col(&self, file: FileId, offset: usize) -> (usize, usize) {
+        let content = &self.file(file).content;
+        let before = &content[..offset.min(content.len())];
+        let line = before.matches('\n').count() + 1;
+        let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+        (line, before[line_start..].chars().count() + 1)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub message: String,
+    pub span: Span,
+    /// Text printed under the highlighted code
+    pub label: Option<String>,
+    pub help: Option<String>,
+    /// Secondary location shown below the main snippet, e.g. a previous definition
+    pub note: Option<Box<(Span, String)>>,
+}
+
+impl Diagnostic {
+    pub fn error(message: impl Into<String>, span: Span) -> Self {
+        Self {
+            severity: Severity::Error,
+            message: message.into(),
+            span,
+            label: None,
+            help: None,
+            note: None,
+        }
+    }
+
+    pub fn warning(message: impl Into<String>, span: Span) -> Self {
+        Self {
+            severity: Severity::Warning,
+            ..Self::error(message, span)
+        }
+    }
+
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    pub fn with_help(mut self, help: impl Into<String>) -> Self {
+        self.help = Some(help.into());
+        self
+    }
+
+    pub fn with_note(mut self, span: Span, message: impl Into<String>) -> Self {
+        self.note = Some(Box::new((span, message.into())));
+        self
+    }
+
+    /// Render the diagnostic rustc-style, with the offending line of code
+    pub fn render(&self, sources: &SourceMap) -> String {
+        let severity = match self.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        };
+
+        let mut out = String::new();
+        let _ = writeln!(out, "{}: {}", severity, self.message);
+        let gutter = render_snippet(&mut out, sources, self.span, self.label.as_deref());
+        if let Some(help) = &self.help {
+            let _ = writeln!(out, "{} |", gutter);
+            let _ = writeln!(out, "{} = help: {}", gutter, help);
+        }
+        if let Some(note) = &self.note {
+            let (span, message) = note.as_ref();
+            let _ = writeln!(out, "note: {}", message);
+            render_snippet(&mut out, sources, *span, None);
+        }
+        out
+    }
+}
+
+/// Write the `--> file:line:col` header and the underlined source line, returning the gutter
+fn render_snippet(out: &mut String, sources: &SourceMap, span: Span, label: Option<&str>) -> String {
+    let file = sources.file(span.file);
+    let (line, column) = sources.line_col(span.file, span.start);
+    let source_line = file.content.lines().nth(line - 1).unwrap_or("");
+    let gutter = " ".repeat(line.to_string().len());
+
+    // Underline up to the end of the first line of the span
+    let line_rest = source_line.chars().count().saturating_sub(column - 1);
+    let span_len = file.content[span.start.min(file.content.len())..span.end.min(file.content.len())]
+        .chars()
+        .count();
+    let underline = "^".repeat(span_len.min(line_rest).max(1));
+
+    let _ = writeln!(out, "{}--> {}:{}:{}", gutter, file.name, line, column);
+    let _ = writeln!(out, "{} |", gutter);
+    let _ = writeln!(out, "{} | {}", line, source_line);
+    let _ = write!(out, "{} | {}{}", gutter, " ".repeat(column - 1), underline);
+    if let Some(label) = label {
+        let _ = write!(out, " {}", label);
+    }
+    out.push('\n');
+    gutter
+}
+
+/// Diagnostics collected while reading the schema
+#[derive(Debug, Default, thiserror::Error)]
+#[error("schema has {} error(s)", self.error_count())]
+pub struct Diagnostics {
+    items: Vec<Diagnostic>,
+}
+
+impl Diagnostics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, diagnostic: Diagnostic) {
+        self.items.push(diagnostic);
+    }
+
+    pub fn error_count(&self) -> usize {
+        self.items.iter().filter(|d| d.severity == Severity::Error).count()
+    }
+
+    pub fn has_errors(&self) -> bool {
+        self.error_count() > 0
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Render every diagnostic, separated by blank lines
+    pub fn render(&self, sources: &SourceMap) -> String {
+        self.items
+            .iter()
+            .map(|d| d.render(sources))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// Closest candidate within a small edit distance, used for "did you mean" hints
+pub fn suggest<'a>(name: &str, candidates: impl IntoIterator<Item = &'a str>) -> Option<&'a str> {
+    candidates
+        .into_iter()
+        .map(|candidate| (edit_distance(&name.to_lowercase(), &candidate.to_lowercase()), candidate))
+        .filter(|(distance, candidate)| *distance <= (candidate.len() / 3).max(1))
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+
+    previous[b.len()]
+}
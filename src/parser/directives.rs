@@ -0,0 +1,128 @@
+use super::diagnostics::suggest;
+use super::FieldDirectives;
+use regex::Regex;
+use std::sync::LazyLock;
+
+const DIRECTIVES: [&str; 3] = ["omit", "readonly", "name"];
+
+const OMIT_TARGETS: [&str; 7] = ["input", "output", "create", "update", "where", "filter", "orderBy"];
+
+static DIRECTIVE_PATTERN: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"@pothos\.(\w+)(?:\(([^)]*)\))?").expect("valid directive pattern"));
+
+/// A malformed `@pothos.*` directive
+pub struct DirectiveError {
+    pub message: String,
+    pub help: Option<String>,
+}
+
+impl DirectiveError {
+    fn new(message: impl Into<String>, help: Option<String>) -> Self {
+        Self {
+            message: message.into(),
+            help,
+        }
+    }
+}
+
+/// Apply the `@pothos.*` directives written in one doc comment line
+/// Returns the line without its directives, which is what remains of the description
+pub fn apply_directives(line: &str, directives: &mut FieldDirectives) -> (String, Vec<DirectiveError>) {
+    let pattern = &*DIRECTIVE_PATTERN;
+    let mut errors = Vec::new();
+
+    for captures in pattern.captures_iter(line) {
+        let name = &captures[1];
+        let arguments = captures.get(2).map(|m| m.as_str().trim());
+
+        match (name, arguments) {
+            ("omit", None) => {
+                directives.omit_output = true;
+                omit_inputs(directives);
+            }
+            ("omit", Some(targets)) => {
+                for target in targets.split(',').map(str::trim) {
+                    match target {
+                        "input" => omit_inputs(directives),
+                        "output" => directives.omit_output = true,
+                        "create" => directives.omit_create = true,
+                        "update" => directives.omit_update = true,
+                        "where" | "filter" => directives.omit_where = true,
+                        "orderBy" => directives.omit_order_by = true,
+                        other => errors.push(DirectiveError::new(
+                            format!("unknown `@pothos.omit` target `{}`", other),
+                            Some(match suggest(other, OMIT_TARGETS) {
+                                Some(candidate) => format!("did you mean `{}`?", candidate),
+                                None => format!("expected one of {}", OMIT_TARGETS.join(", ")),
+                            }),
+                        )),
+                    }
+                }
+            }
+            ("readonly", None) => {
+                directives.omit_create = true;
+                directives.omit_update = true;
+            }
+            ("name", Some(argument)) => match graphql_name(argument) {
+                Some(name) => directives.name = Some(name.to_string()),
+                None => errors.push(DirectiveError::new(
+                    format!("invalid field name `{}` in `@pothos.name`", argument),
+                    Some("expected a GraphQL name in quotes, e.g. `@pothos.name(\"displayName\")`".to_string()),
+                )),
+            },
+            ("readonly", Some(_)) => errors.push(DirectiveError::new(
+                "`@pothos.readonly` does not take arguments",
+                None,
+            )),
+            ("name", None) => errors.push(DirectiveError::new(
+                "`@pothos.name` needs the new field name",
+                Some("for example `@pothos.name(\"displayName\")`".to_string()),
+            )),
+            (other, _) => errors.push(DirectiveError::new(
+                format!("unknown directive `@pothos.{}`", other),
+                Some(match suggest(other, DIRECTIVES) {
+                    Some(candidate) => format!("did you mean `@pothos.{}`?", candidate),
+                    None => "expected `@pothos.omit`, `@pothos.readonly` or `@pothos.name`".to_string(),
+                }),
+            )),
+        }
+    }
+
+    let rest = pattern.replace_all(line, "").trim().to_string();
+    (rest, errors)
+}
+
+/// Split a field's documentation into its description and directives
+/// Used for DMMF, where doc comments arrive as one string without source positions
+pub fn parse_documentation(text: &str) -> (Option<String>, FieldDirectives, Vec<DirectiveError>) {
+    let mut directives = FieldDirectives::default();
+    let mut lines = Vec::new();
+    let mut errors = Vec::new();
+
+    for line in text.lines() {
+        let (rest, line_errors) = apply_directives(line, &mut directives);
+        errors.extend(line_errors);
+        if !rest.is_empty() {
+            lines.push(rest);
+        }
+    }
+
+    let description = if lines.is_empty() { None } else { Some(lines.join("\n")) };
+    (description, directives, errors)
+}
+
+fn omit_inputs(directives: &mut FieldDirectives) {
+    directives.omit_create = true;
+    directives.omit_update = true;
+    directives.omit_where = true;
+    directives.omit_order_by = true;
+}
+
+/// The name inside `"..."` if it is a valid GraphQL name
+fn graphql_name(argument: &str) -> Option<&str> {
+    let name = argument.strip_prefix('"')?.strip_suffix('"')?;
+    let mut chars = name.chars();
+    let valid = chars.next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+    valid.then_some(name)
+}
@@ -0,0 +1,158 @@
+use super::diagnostics::SourceMap;
+use anyhow::{bail, Result};
+use regex::Regex;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// Load every schema file selected by `path` into a `SourceMap`
+///
+/// `path` may be a single file, a directory (all `.prisma` files below it, as
+/// used by `prismaSchemaFolder`) or a glob such as `prisma/schema/**/*.prisma`.
+pub fn load_schema_files(path: &Path) -> Result<SourceMap> {
+    let paths = resolve_schema_paths(path)?;
+
+    let mut sources = SourceMap::new();
+    for path in &paths {
+        sources.load(path)?;
+    }
+
+    Ok(sources)
+}
+
+fn resolve_schema_paths(path: &Path) -> Result<Vec<PathBuf>> {
+    let raw = path.to_string_lossy();
+
+    let mut paths = if raw.contains(['*', '?', '[']) {
+        glob_paths(&raw)?
+    } else if path.is_dir() {
+        WalkDir::new(path)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file() && is_prisma_file(e.path()))
+            .map(|e| e.into_path())
+            .collect()
+    } else if path.is_file() {
+        vec![path.to_path_buf()]
+    } else {
+        bail!("schema path {:?} does not exist", path);
+    };
+
+    if paths.is_empty() {
+        bail!("no .prisma files found for {:?}", path);
+    }
+
+    // Sort so that generated output does not depend on directory iteration order
+    paths.sort();
+    Ok(paths)
+}
+
+fn is_prisma_file(path: &Path) -> bool {
+    path.extension().and_then(|s| s.to_str()) == Some("prisma")
+}
+
+/// Expand a glob by walking its literal base directory and matching relative paths
+fn glob_paths(pattern: &str) -> Result<Vec<PathBuf>> {
+    let pattern = pattern.replace('\\', "/");
+    let glob_start = pattern.find(['*', '?', '[']).unwrap_or(pattern.len());
+    let base_end = pattern[..glob_start].rfind('/').map(|i| i + 1).unwrap_or(0);
+    let base = if base_end == 0 { "." } else { &pattern[..base_end] };
+    let matcher = glob_to_regex(&pattern[base_end..])?;
+
+    Ok(WalkDir::new(base)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter(|e| {
+            let relative = e.path().strip_prefix(base).unwrap_or(e.path());
+            matcher.is_match(&relative.to_string_lossy().replace('\\', "/"))
+        })
+        .map(|e| e.into_path())
+        .collect())
+}
+
+/// Translate `*`, `**`, `?` and `[...]` glob syntax into an anchored regex
+fn glob_to_regex(glob: &str) -> Result<Regex> {
+    let mut regex = String::from("^");
+    let mut chars = glob.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                // `**/` also matches zero directories
+                if chars.peek() == Some(&'/') {
+                    chars.next();
+                    regex.push_str("(?:.*/)?");
+                } else {
+                    regex.push_str(".*");
+                }
+            }
+            '*' => regex.push_str("[^/]*"),
+            '?' => regex.push_str("[^/]"),
+            '[' => {
+                let rest: String = chars.clone().collect();
+                // A `]` right after `[` or `[!` is part of the class
+                let skip = if rest.starts_with('!') { 2 } else { 1 };
+                match rest.char_indices().skip(skip).find(|&(_, c)| c == ']') {
+                    Some((end, _)) => {
+                        regex.push_str(&class_to_regex(&rest[..end]));
+                        for _ in rest[..=end].chars() {
+                            chars.next();
+                        }
+                    }
+                    // Without a closing `]` the bracket is literal
+                    None => regex.push_str(r"\["),
+                }
+            }
+            other => regex.push_str(&regex::escape(&other.to_string())),
+        }
+    }
+
+    regex.push('$');
+    Ok(Regex::new(&regex)?)
+}
+
+/// Translate the inside of a `[...]` glob class: a leading `!` negates it and
+/// everything but `-` ranges is matched literally
+fn class_to_regex(class: &str) -> String {
+    let (negated, class) = match class.strip_prefix('!') {
+        Some(rest) => (true, rest),
+        None => (false, class),
+    };
+
+    let mut regex = String::from(if negated { "[^/" } else { "[" });
+    for c in class.chars() {
+        if c == '-' {
+            regex.push('-');
+        } else {
+            regex.push_str(&regex::escape(&c.to_string()));
+        }
+    }
+    regex.push(']');
+    regex
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn translates_glob_classes() {
+        let matcher = glob_to_regex("schema/[ab]*.prisma").unwrap();
+        assert!(matcher.is_match("schema/auth.prisma"));
+        assert!(!matcher.is_match("schema/user.prisma"));
+
+        let matcher = glob_to_regex("schema/[!ab]*.prisma").unwrap();
+        assert!(matcher.is_match("schema/user.prisma"));
+        assert!(!matcher.is_match("schema/auth.prisma"));
+        assert!(!matcher.is_match("schema/blog.prisma"));
+
+        let matcher = glob_to_regex("[a-c^]*.prisma").unwrap();
+        assert!(matcher.is_match("b.prisma"));
+        assert!(matcher.is_match("^.prisma"));
+        assert!(!matcher.is_match("d.prisma"));
+
+        let matcher = glob_to_regex("[*.prisma").unwrap();
+        assert!(matcher.is_match("[x.prisma"));
+    }
+}
@@ -0,0 +1,554 @@
+use super::ast::*;
+use super::diagnostics::{Diagnostic, Span};
+use super::lexer::{Token, TokenKind};
+
+type ParseResult<T> = Result<T, Diagnostic>;
+
+/// Parse a token stream produced by `lexer::tokenize` into a schema AST
+pub fn parse(tokens: &[Token]) -> ParseResult<SchemaAst> {
+    let mut parser = Parser { tokens, pos: 0 };
+    parser.parse_schema()
+}
+
+const BLOCK_KEYWORDS: [&str; 6] = ["model", "view", "type", "enum", "datasource", "generator"];
+
+struct Parser<'a> {
+    tokens: &'a [Token],
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> &TokenKind {
+        &self.peek_token().kind
+    }
+
+    fn peek_token(&self) -> &Token {
+        self.peek_nth(0)
+    }
+
+    fn peek_nth(&self, n: usize) -> &Token {
+        let index = (self.pos + n).min(self.tokens.len() - 1);
+        &self.tokens[index]
+    }
+
+    fn bump(&mut self) -> Token {
+        let token = self.peek_token().clone();
+        if self.pos < self.tokens.len() - 1 {
+            self.pos += 1;
+        }
+        token
+    }
+
+    fn expect(&mut self, kind: TokenKind, context: &str) -> ParseResult<Span> {
+        if *self.peek() == kind {
+            Ok(self.bump().span)
+        } else {
+            let found = self.peek_token();
+            Err(Diagnostic::error(
+                format!("expected {} {}, found {}", kind, context, found.kind),
+                found.span,
+            ))
+        }
+    }
+
+    fn expect_ident(&mut self, what: &str) -> ParseResult<Ident> {
+        match self.peek().clone() {
+            TokenKind::Ident(name) => {
+                let span = self.bump().span;
+                Ok(Ident { name, span })
+            }
+            other => Err(Diagnostic::error(
+                format!("expected {}, found {}", what, other),
+                self.peek_token().span,
+            )),
+        }
+    }
+
+    fn skip_newlines(&mut self) {
+        while *self.peek() == TokenKind::Newline {
+            self.bump();
+        }
+    }
+
+    /// Inside parentheses and brackets newlines and comments carry no meaning
+    fn skip_trivia(&mut self) {
+        while matches!(
+            self.peek(),
+            TokenKind::Newline | TokenKind::Comment(_) | TokenKind::DocComment(_)
+        ) {
+            self.bump();
+        }
+    }
+
+    /// Take the comment token under the cursor, if any
+    fn eat_comment(&mut self) -> Option<Comment> {
+        let (text, is_doc) = match self.peek() {
+            TokenKind::Comment(text) => (text.clone(), false),
+            TokenKind::DocComment(text) => (text.clone(), true),
+            _ => return None,
+        };
+        let span = self.bump().span;
+        Some(Comment { text, is_doc, span })
+    }
+
+    /// Consume trailing comments and the newline ending a declaration
+    fn finish_line(&mut self, comments: &mut Vec<Comment>) -> ParseResult<()> {
+        while let Some(comment) = self.eat_comment() {
+            comments.push(comment);
+        }
+        match self.peek() {
+            TokenKind::Newline => {
+                self.bump();
+                Ok(())
+            }
+            TokenKind::RBrace | TokenKind::Eof => Ok(()),
+            other => Err(Diagnostic::error(
+                format!("expected end of line, found {}", other),
+                self.peek_token().span,
+            )),
+        }
+    }
+
+    fn parse_schema(&mut self) -> ParseResult<SchemaAst> {
+        let mut ast = SchemaAst::default();
+        let mut pending_comments = Vec::new();
+
+        loop {
+            if let Some(comment) = self.eat_comment() {
+                pending_comments.push(comment);
+                continue;
+            }
+
+            match self.peek().clone() {
+                TokenKind::Eof => break,
+                TokenKind::Newline => {
+                    self.bump();
+                }
+                TokenKind::Ident(keyword) => {
+                    let comments = std::mem::take(&mut pending_comments);
+                    let top = match keyword.as_str() {
+                        "model" => Top::Model(self.parse_model(ModelKind::Model, comments)?),
+                        "view" => Top::Model(self.parse_model(ModelKind::View, comments)?),
+                        "type" => Top::Model(self.parse_model(ModelKind::Type, comments)?),
+                        "enum" => Top::Enum(self.parse_enum()?),
+                        "datasource" => Top::Datasource(self.parse_config_block()?),
+                        "generator" => Top::Generator(self.parse_config_block()?),
+                        _ => {
+                            return Err(Diagnostic::error(
+                                format!(
+                                    "unexpected `{}`, expected a `model`, `enum`, `datasource` or `generator` block",
+                                    keyword
+                                ),
+                                self.peek_token().span,
+                            ))
+                        }
+                    };
+                    ast.tops.push(top);
+                }
+                other => {
+                    return Err(Diagnostic::error(
+                        format!("unexpected {}, expected a `model`, `enum`, `datasource` or `generator` block", other),
+                        self.peek_token().span,
+                    ))
+                }
+            }
+        }
+
+        Ok(ast)
+    }
+
+    /// Parse `keyword Name {`, allowing the brace on a following line
+    fn parse_block_header(&mut self) -> ParseResult<(Span, Ident, Span)> {
+        let keyword = self.bump();
+        let name = self.expect_ident("a block name")?;
+        self.skip_newlines();
+        let context = format!("after `{}`", name.name);
+        let open = self.expect(TokenKind::LBrace, &context)?;
+        Ok((keyword.span, name, open))
+    }
+
+    /// Consumes the closing brace and returns whether the block ended, or an "unterminated block"
+    /// error if the file ends (or another block starts) before it
+    fn block_end(&mut self, keyword_span: Span, name: &Ident, open: Span) -> ParseResult<bool> {
+        let starts_new_block = matches!(self.peek(), TokenKind::Ident(kw) if BLOCK_KEYWORDS.contains(&kw.as_str()))
+            && matches!(self.peek_nth(1).kind, TokenKind::Ident(_))
+            && (self.peek_nth(2).kind == TokenKind::LBrace
+                || (self.peek_nth(2).kind == TokenKind::Newline && self.peek_nth(3).kind == TokenKind::LBrace));
+
+        match self.peek() {
+            TokenKind::RBrace => {
+                self.bump();
+                Ok(true)
+            }
+            TokenKind::Eof => Err(unterminated(keyword_span, name, open)),
+            _ if starts_new_block => Err(unterminated(keyword_span, name, open)),
+            _ => Ok(false),
+        }
+    }
+
+    fn parse_model(&mut self, kind: ModelKind, comments: Vec<Comment>) -> ParseResult<ModelBlock> {
+        let (keyword_span, name, open) = self.parse_block_header()?;
+        let mut fields = Vec::new();
+        let mut attributes = Vec::new();
+        let mut pending_comments = Vec::new();
+
+        loop {
+            if self.block_end(keyword_span, &name, open)? {
+                break;
+            }
+            if let Some(comment) = self.eat_comment() {
+                pending_comments.push(comment);
+                continue;
+            }
+
+            match self.peek() {
+                TokenKind::Newline => {
+                    self.bump();
+                }
+                TokenKind::AtAt => {
+                    attributes.push(self.parse_attribute()?);
+                    self.finish_line(&mut pending_comments)?;
+                    pending_comments.clear();
+                }
+                TokenKind::Ident(_) => {
+                    let mut field = self.parse_field(std::mem::take(&mut pending_comments))?;
+                    self.finish_line(&mut field.comments)?;
+                    fields.push(field);
+                }
+                other => {
+                    return Err(Diagnostic::error(
+                        format!("unexpected {} in `{}`, expected a field or block attribute", other, name.name),
+                        self.peek_token().span,
+                    ))
+                }
+            }
+        }
+
+        Ok(ModelBlock {
+            kind,
+            name,
+            fields,
+            attributes,
+            comments,
+        })
+    }
+
+    fn parse_field(&mut self, comments: Vec<Comment>) -> ParseResult<FieldDecl> {
+        let name = self.expect_ident("a field name")?;
+
+        let type_name = match self.peek() {
+            TokenKind::Ident(_) => self.expect_ident("a field type")?,
+            _ => {
+                return Err(Diagnostic::error(
+                    format!("field `{}` is missing a type", name.name),
+                    name.span,
+                ))
+            }
+        };
+        let mut type_span = type_name.span;
+
+        let mut unsupported = None;
+        if type_name.name == "Unsupported" && *self.peek() == TokenKind::LParen {
+            self.bump();
+            match self.peek().clone() {
+                TokenKind::String(value) => {
+                    self.bump();
+                    unsupported = Some(value);
+                }
+                other => {
+                    return Err(Diagnostic::error(
+                        format!("expected a string inside `Unsupported(...)`, found {}", other),
+                        self.peek_token().span,
+                    ))
+                }
+            }
+            type_span = type_span.to(self.expect(TokenKind::RParen, "to close `Unsupported(`")?);
+        }
+
+        let arity = match self.peek() {
+            TokenKind::LBracket => {
+                self.bump();
+                type_span = type_span.to(self.expect(TokenKind::RBracket, "after `[` in list type")?);
+                Arity::List
+            }
+            TokenKind::Question => {
+                type_span = type_span.to(self.bump().span);
+                Arity::Optional
+            }
+            _ => Arity::Required,
+        };
+
+        let mut attributes = Vec::new();
+        while *self.peek() == TokenKind::At {
+            attributes.push(self.parse_attribute()?);
+        }
+
+        Ok(FieldDecl {
+            name,
+            field_type: TypeRef {
+                name: type_name,
+                arity,
+                unsupported,
+                span: type_span,
+            },
+            attributes,
+            comments,
+        })
+    }
+
+    fn parse_enum(&mut self) -> ParseResult<EnumBlock> {
+        let (keyword_span, name, open) = self.parse_block_header()?;
+        let mut values = Vec::new();
+        let mut attributes = Vec::new();
+        let mut pending_comments = Vec::new();
+
+        loop {
+            if self.block_end(keyword_span, &name, open)? {
+                break;
+            }
+            if let Some(comment) = self.eat_comment() {
+                pending_comments.push(comment);
+                continue;
+            }
+
+            match self.peek() {
+                TokenKind::Newline => {
+                    self.bump();
+                }
+                TokenKind::AtAt => {
+                    attributes.push(self.parse_attribute()?);
+                    self.finish_line(&mut pending_comments)?;
+                    pending_comments.clear();
+                }
+                TokenKind::Ident(_) => {
+                    let value_name = self.expect_ident("an enum value")?;
+                    let mut value_attributes = Vec::new();
+                    while *self.peek() == TokenKind::At {
+                        value_attributes.push(self.parse_attribute()?);
+                    }
+                    let mut value = EnumValueDecl {
+                        name: value_name,
+                        attributes: value_attributes,
+                        comments: std::mem::take(&mut pending_comments),
+                    };
+                    self.finish_line(&mut value.comments)?;
+                    values.push(value);
+                }
+                other => {
+                    return Err(Diagnostic::error(
+                        format!("unexpected {} in enum `{}`, expected a value", other, name.name),
+                        self.peek_token().span,
+                    ))
+                }
+            }
+        }
+
+        Ok(EnumBlock {
+            name,
+            values,
+            attributes,
+        })
+    }
+
+    fn parse_config_block(&mut self) -> ParseResult<ConfigBlock> {
+        let (keyword_span, name, open) = self.parse_block_header()?;
+        let mut properties = Vec::new();
+        let mut pending_comments = Vec::new();
+
+        loop {
+            if self.block_end(keyword_span, &name, open)? {
+                break;
+            }
+            if let Some(comment) = self.eat_comment() {
+                pending_comments.push(comment);
+                continue;
+            }
+
+            match self.peek() {
+                TokenKind::Newline => {
+                    self.bump();
+                }
+                TokenKind::Ident(_) => {
+                    let key = self.expect_ident("a property name")?;
+                    let context = format!("after `{}`", key.name);
+                    self.expect(TokenKind::Equals, &context)?;
+                    let value = self.parse_expression()?;
+                    let mut property = Property {
+                        name: key,
+                        value,
+                        comments: std::mem::take(&mut pending_comments),
+                    };
+                    self.finish_line(&mut property.comments)?;
+                    properties.push(property);
+                }
+                other => {
+                    return Err(Diagnostic::error(
+                        format!("unexpected {} in `{}`, expected `key = value`", other, name.name),
+                        self.peek_token().span,
+                    ))
+                }
+            }
+        }
+
+        Ok(ConfigBlock { name, properties })
+    }
+
+    /// Parse `@name`, `@@name`, `@db.VarChar(255)` with optional arguments
+    fn parse_attribute(&mut self) -> ParseResult<Attribute> {
+        let at = self.bump();
+        let mut name = self.expect_ident("an attribute name")?;
+
+        while *self.peek() == TokenKind::Dot {
+            self.bump();
+            let segment = self.expect_ident("an attribute name after `.`")?;
+            name.name.push('.');
+            name.name.push_str(&segment.name);
+            name.span = name.span.to(segment.span);
+        }
+
+        let (arguments, end) = if *self.peek() == TokenKind::LParen {
+            self.parse_arguments()?
+        } else {
+            (Vec::new(), name.span)
+        };
+
+        Ok(Attribute {
+            name,
+            arguments,
+            span: at.span.to(end),
+        })
+    }
+
+    /// Parse a parenthesized argument list, returning the arguments and the closing paren span
+    fn parse_arguments(&mut self) -> ParseResult<(Vec<Argument>, Span)> {
+        let open = self.bump().span;
+        let mut arguments = Vec::new();
+
+        loop {
+            self.skip_trivia();
+            match self.peek() {
+                TokenKind::RParen => return Ok((arguments, self.bump().span)),
+                TokenKind::Eof => return Err(Diagnostic::error("unclosed `(`", open).with_label("this parenthesis is never closed")),
+                _ => {}
+            }
+
+            arguments.push(self.parse_argument()?);
+
+            self.skip_trivia();
+            match self.peek() {
+                TokenKind::Comma => {
+                    self.bump();
+                }
+                TokenKind::RParen => {}
+                TokenKind::Eof => return Err(Diagnostic::error("unclosed `(`", open).with_label("this parenthesis is never closed")),
+                other => {
+                    return Err(Diagnostic::error(
+                        format!("expected `,` or `)` in argument list, found {}", other),
+                        self.peek_token().span,
+                    ))
+                }
+            }
+        }
+    }
+
+    fn parse_argument(&mut self) -> ParseResult<Argument> {
+        let is_named = matches!(self.peek(), TokenKind::Ident(_)) && self.peek_nth(1).kind == TokenKind::Colon;
+
+        if is_named {
+            let name = self.expect_ident("an argument name")?;
+            self.bump();
+            self.skip_trivia();
+            let value = self.parse_expression()?;
+            Ok(Argument {
+                span: name.span.to(value.span()),
+                name: Some(name),
+                value,
+            })
+        } else {
+            let value = self.parse_expression()?;
+            Ok(Argument {
+                span: value.span(),
+                name: None,
+                value,
+            })
+        }
+    }
+
+    fn parse_expression(&mut self) -> ParseResult<Expression> {
+        let token = self.peek_token().clone();
+
+        match token.kind {
+            TokenKind::String(value) => {
+                self.bump();
+                Ok(Expression::String(value, token.span))
+            }
+            TokenKind::Number(value) => {
+                self.bump();
+                Ok(Expression::Number(value, token.span))
+            }
+            TokenKind::Ident(mut name) => {
+                self.bump();
+                let mut span = token.span;
+                while *self.peek() == TokenKind::Dot {
+                    self.bump();
+                    let segment = self.expect_ident("a name after `.`")?;
+                    name.push('.');
+                    name.push_str(&segment.name);
+                    span = span.to(segment.span);
+                }
+
+                if *self.peek() == TokenKind::LParen {
+                    let (arguments, close) = self.parse_arguments()?;
+                    Ok(Expression::Function(name, arguments, span.to(close)))
+                } else {
+                    Ok(Expression::Constant(name, span))
+                }
+            }
+            TokenKind::LBracket => {
+                self.bump();
+                let mut items = Vec::new();
+
+                loop {
+                    self.skip_trivia();
+                    match self.peek() {
+                        TokenKind::RBracket => {
+                            let close = self.bump().span;
+                            return Ok(Expression::Array(items, token.span.to(close)));
+                        }
+                        TokenKind::Eof => return Err(Diagnostic::error("unclosed `[`", token.span).with_label("this bracket is never closed")),
+                        _ => {}
+                    }
+
+                    items.push(self.parse_expression()?);
+
+                    self.skip_trivia();
+                    match self.peek() {
+                        TokenKind::Comma => {
+                            self.bump();
+                        }
+                        TokenKind::RBracket => {}
+                        TokenKind::Eof => return Err(Diagnostic::error("unclosed `[`", token.span).with_label("this bracket is never closed")),
+                        other => {
+                            return Err(Diagnostic::error(
+                                format!("expected `,` or `]` in list, found {}", other),
+                                self.peek_token().span,
+                            ))
+                        }
+                    }
+                }
+            }
+            other => Err(Diagnostic::error(
+                format!("expected a value, found {}", other),
+                token.span,
+            )),
+        }
+    }
+}
+
+fn unterminated(keyword_span: Span, name: &Ident, open: Span) -> Diagnostic {
+    Diagnostic::error(
+        format!("unterminated block `{}`", name.name),
+        keyword_span.to(open),
+    )
+    .with_label("this block is missing its closing `}`")
+}
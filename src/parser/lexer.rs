@@ -0,0 +1,247 @@
+use super::diagnostics::{Diagnostic, FileId, Span};
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TokenKind {
+    Ident(String),
+    String(String),
+    Number(String),
+    /// `/// text` comment, attached to the next declaration
+    DocComment(String),
+    /// `// text` comment
+    Comment(String),
+    LBrace,
+    RBrace,
+    LParen,
+    RParen,
+    LBracket,
+    RBracket,
+    Comma,
+    Colon,
+    Equals,
+    Question,
+    Dot,
+    At,
+    AtAt,
+    Newline,
+    Eof,
+}
+
+impl fmt::Display for TokenKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TokenKind::Ident(name) => write!(f, "`{}`", name),
+            TokenKind::String(_) => write!(f, "string literal"),
+            TokenKind::Number(n) => write!(f, "`{}`", n),
+            TokenKind::DocComment(_) | TokenKind::Comment(_) => write!(f, "comment"),
+            TokenKind::LBrace => write!(f, "`{{`"),
+            TokenKind::RBrace => write!(f, "`}}`"),
+            TokenKind::LParen => write!(f, "`(`"),
+            TokenKind::RParen => write!(f, "`)`"),
+            TokenKind::LBracket => write!(f, "`[`"),
+            TokenKind::RBracket => write!(f, "`]`"),
+            TokenKind::Comma => write!(f, "`,`"),
+            TokenKind::Colon => write!(f, "`:`"),
+            TokenKind::Equals => write!(f, "`=`"),
+            TokenKind::Question => write!(f, "`?`"),
+            TokenKind::Dot => write!(f, "`.`"),
+            TokenKind::At => write!(f, "`@`"),
+            TokenKind::AtAt => write!(f, "`@@`"),
+            TokenKind::Newline => write!(f, "end of line"),
+            TokenKind::Eof => write!(f, "end of file"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Token {
+    pub kind: TokenKind,
+    pub span: Span,
+}
+
+/// Split a Prisma schema into tokens
+/// Newlines are significant (they terminate fields and properties), other whitespace is dropped
+pub fn tokenize(source: &str, file: FileId) -> Result<Vec<Token>, Diagnostic> {
+    let bytes = source.as_bytes();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let c = bytes[i];
+        let start = i;
+
+        let kind = match c {
+            b'\n' => {
+                i += 1;
+                TokenKind::Newline
+            }
+            b' ' | b'\t' | b'\r' => {
+                i += 1;
+                continue;
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'/') => {
+                let is_doc = bytes.get(i + 2) == Some(&b'/');
+                let text_start = if is_doc { i + 3 } else { i + 2 };
+                while i < bytes.len() && bytes[i] != b'\n' {
+                    i += 1;
+                }
+                let text = source[text_start..i].trim().to_string();
+                if is_doc {
+                    TokenKind::DocComment(text)
+                } else {
+                    TokenKind::Comment(text)
+                }
+            }
+            b'"' => {
+                let (value, end) = lex_string(source, file, i)?;
+                i = end;
+                TokenKind::String(value)
+            }
+            b'{' => single(&mut i, TokenKind::LBrace),
+            b'}' => single(&mut i, TokenKind::RBrace),
+            b'(' => single(&mut i, TokenKind::LParen),
+            b')' => single(&mut i, TokenKind::RParen),
+            b'[' => single(&mut i, TokenKind::LBracket),
+            b']' => single(&mut i, TokenKind::RBracket),
+            b',' => single(&mut i, TokenKind::Comma),
+            b':' => single(&mut i, TokenKind::Colon),
+            b'=' => single(&mut i, TokenKind::Equals),
+            b'?' => single(&mut i, TokenKind::Question),
+            b'.' => single(&mut i, TokenKind::Dot),
+            b'@' => {
+                if bytes.get(i + 1) == Some(&b'@') {
+                    i += 2;
+                    TokenKind::AtAt
+                } else {
+                    i += 1;
+                    TokenKind::At
+                }
+            }
+            // `-?[0-9]+(\.[0-9]+)?`
+            b'-' | b'0'..=b'9' => {
+                i += 1;
+                skip_digits(bytes, &mut i);
+                if bytes.get(i) == Some(&b'.') && bytes.get(i + 1).is_some_and(u8::is_ascii_digit) {
+                    i += 1;
+                    skip_digits(bytes, &mut i);
+                }
+                if &source[start..i] == "-" {
+                    return Err(Diagnostic::error("unexpected character `-`", Span::new(file, start, i)));
+                }
+                // A second fraction (`1.2.3`) or letters glued to the digits (`12ab`)
+                if i < bytes.len() && (bytes[i] == b'.' || is_ident_char(bytes[i])) {
+                    let mut end = i;
+                    while end < bytes.len() && (bytes[end] == b'.' || is_ident_char(bytes[end])) {
+                        end += 1;
+                    }
+                    return Err(Diagnostic::error(
+                        format!("invalid number `{}`", &source[start..end]),
+                        Span::new(file, start, end),
+                    ));
+                }
+                TokenKind::Number(source[start..i].to_string())
+            }
+            // `[A-Za-z_][A-Za-z0-9_]*`
+            c if c.is_ascii_alphabetic() || c == b'_' => {
+                while i < bytes.len() && is_ident_char(bytes[i]) {
+                    i += 1;
+                }
+                TokenKind::Ident(source[start..i].to_string())
+            }
+            _ => {
+                let ch = source[i..].chars().next().unwrap_or('?');
+                return Err(Diagnostic::error(
+                    format!("unexpected character `{}`", ch),
+                    Span::new(file, i, i + ch.len_utf8()),
+                ));
+            }
+        };
+
+        tokens.push(Token {
+            kind,
+            span: Span::new(file, start, i),
+        });
+    }
+
+    tokens.push(Token {
+        kind: TokenKind::Eof,
+        span: Span::new(file, bytes.len(), bytes.len()),
+    });
+
+    Ok(tokens)
+}
+
+fn is_ident_char(c: u8) -> bool {
+    c.is_ascii_alphanumeric() || c == b'_'
+}
+
+fn skip_digits(bytes: &[u8], i: &mut usize) {
+    while *i < bytes.len() && bytes[*i].is_ascii_digit() {
+        *i += 1;
+    }
+}
+
+fn single(i: &mut usize, kind: TokenKind) -> TokenKind {
+    *i += 1;
+    kind
+}
+
+/// Lex a double-quoted string starting at `start`, returning its unescaped value and the end offset
+fn lex_string(source: &str, file: FileId, start: usize) -> Result<(String, usize), Diagnostic> {
+    let mut value = String::new();
+    let mut chars = source[start + 1..].char_indices();
+
+    while let Some((offset, c)) = chars.next() {
+        let pos = start + 1 + offset;
+        match c {
+            '"' => return Ok((value, pos + 1)),
+            '\n' => break,
+            '\\' => match chars.next() {
+                Some((_, 'n')) => value.push('\n'),
+                Some((_, 't')) => value.push('\t'),
+                Some((_, 'r')) => value.push('\r'),
+                Some((_, other)) => value.push(other),
+                None => break,
+            },
+            other => value.push(other),
+        }
+    }
+
+    let line_end = source[start..].find('\n').map(|e| start + e).unwrap_or(source.len());
+    Err(Diagnostic::error("unterminated string literal", Span::new(file, start, line_end))
+        .with_label("missing closing `\"`"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(source: &str) -> Vec<TokenKind> {
+        tokenize(source, 0).unwrap().into_iter().map(|t| t.kind).collect()
+    }
+
+    #[test]
+    fn lexes_identifiers_and_numbers() {
+        assert_eq!(
+            kinds("id_2 -1.5 42"),
+            [
+                TokenKind::Ident("id_2".to_string()),
+                TokenKind::Number("-1.5".to_string()),
+                TokenKind::Number("42".to_string()),
+                TokenKind::Eof,
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_dash_in_identifier() {
+        let error = tokenize("model a-b {}", 0).unwrap_err();
+        assert_eq!(error.message, "unexpected character `-`");
+    }
+
+    #[test]
+    fn rejects_number_with_two_fractions() {
+        let error = tokenize("x 1.2.3", 0).unwrap_err();
+        assert_eq!(error.message, "invalid number `1.2.3`");
+    }
+}
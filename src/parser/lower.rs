@@ -0,0 +1,703 @@
+use super::ast::{Arity, Attribute, Comment, ConfigBlock, Expression, FieldDecl, ModelBlock, ModelKind, SchemaAst, Top};
+use super::diagnostics::{suggest, Diagnostic, Diagnostics, Span};
+use super::directives::apply_directives;
+use super::{
+    Datasource, Enum, EnumValue, Field, FieldDirectives, FieldType, Generator, Index, Model, ParsedSchema, PrimaryKey,
+    Provider, Relation,
+};
+use serde_json::Value;
+use std::collections::HashMap;
+
+const SCALAR_TYPES: [&str; 9] = [
+    "String", "Int", "Float", "Boolean", "DateTime", "Json", "Decimal", "BigInt", "Bytes",
+];
+
+const FIELD_ATTRIBUTES: [&str; 7] = ["id", "unique", "default", "relation", "updatedAt", "map", "ignore"];
+
+const BLOCK_ATTRIBUTES: [&str; 8] = ["id", "unique", "index", "map", "ignore", "fulltext", "schema", "shardKey"];
+
+const RELATION_ARGUMENTS: [&str; 6] = ["name", "fields", "references", "onDelete", "onUpdate", "map"];
+
+const REFERENTIAL_ACTIONS: [&str; 5] = ["Cascade", "Restrict", "NoAction", "SetNull", "SetDefault"];
+
+/// Names declared in the schema, collected before lowering so that fields can
+/// reference blocks declared later in the file or in another file
+struct Declarations<'a> {
+    models: HashMap<&'a str, &'a ModelBlock>,
+    enums: Vec<&'a str>,
+    /// Composite types and views, which the generators do not handle
+    unsupported: HashMap<&'a str, ModelKind>,
+    ignored_models: Vec<&'a str>,
+    /// Provider of the datasource block, which restricts the allowed field types
+    provider: Option<Provider>,
+}
+
+/// Lower the syntax trees of all schema files into the schema representation used by the generators
+pub fn lower_schema(asts: &[SchemaAst], diagnostics: &mut Diagnostics) -> ParsedSchema {
+    let datasource = lower_datasources(asts, diagnostics);
+    let mut declarations = collect_declarations(asts, diagnostics);
+    declarations.provider = datasource.as_ref().map(|d| d.provider);
+
+    let mut models = Vec::new();
+    let mut enums = Vec::new();
+    let mut generators = Vec::new();
+
+    for top in asts.iter().flat_map(|ast| &ast.tops) {
+        match top {
+            Top::Enum(e) => {
+                check_block_attributes(&e.attributes, diagnostics);
+
+                let values = e
+                    .values
+                    .iter()
+                    .map(|v| EnumValue {
+                        name: v.name.name.clone(),
+                        db_name: string_argument(&v.attributes, "map"),
+                        documentation: documentation(&v.comments),
+                    })
+                    .collect();
+
+                enums.push(Enum {
+                    name: e.name.name.clone(),
+                    values,
+                });
+            }
+            Top::Model(m) if m.kind == ModelKind::Model => {
+                if let Some(model) = lower_model(m, &declarations, diagnostics) {
+                    models.push(model);
+                }
+            }
+            Top::Model(m) => {
+                let kind = if m.kind == ModelKind::View { "view" } else { "composite type" };
+                diagnostics.push(
+                    Diagnostic::warning(format!("{} `{}` is not supported and will be skipped", kind, m.name.name), m.name.span)
+                        .with_label("no GraphQL types are generated for this block"),
+                );
+            }
+            Top::Generator(g) => generators.push(lower_generator(g, diagnostics)),
+            Top::Datasource(_) => {}
+        }
+    }
+
+    ParsedSchema {
+        models,
+        enums,
+        datasource,
+        generators,
+    }
+}
+
+/// Lower the `datasource` block; a schema may define at most one
+fn lower_datasources(asts: &[SchemaAst], diagnostics: &mut Diagnostics) -> Option<Datasource> {
+    let mut datasource = None;
+    let mut first_span = None;
+
+    for top in asts.iter().flat_map(|ast| &ast.tops) {
+        let Top::Datasource(block) = top else {
+            continue;
+        };
+
+        if let Some(previous) = first_span {
+            diagnostics.push(
+                Diagnostic::error("a schema can only have one datasource", block.name.span)
+                    .with_label("second datasource defined here")
+                    .with_note(previous, "first datasource defined here"),
+            );
+            continue;
+        }
+        first_span = Some(block.name.span);
+
+        let Some(property) = block.properties.iter().find(|p| p.name.name == "provider") else {
+            diagnostics.push(
+                Diagnostic::error(format!("datasource `{}` has no `provider`", block.name.name), block.name.span)
+                    .with_help("for example `provider = \"postgresql\"`"),
+            );
+            continue;
+        };
+
+        let Some(name) = property.value.as_str() else {
+            diagnostics.push(Diagnostic::error("`provider` must be a string", property.value.span()));
+            continue;
+        };
+
+        match Provider::from_name(name) {
+            Some(provider) => {
+                datasource = Some(Datasource {
+                    name: block.name.name.clone(),
+                    provider,
+                })
+            }
+            None => {
+                let diagnostic = Diagnostic::error(format!("unknown datasource provider `{}`", name), property.value.span());
+                diagnostics.push(match suggest(name, Provider::NAMES) {
+                    Some(candidate) => diagnostic.with_help(format!("did you mean `{}`?", candidate)),
+                    None => diagnostic.with_help(format!("expected one of {}", Provider::NAMES.join(", "))),
+                });
+            }
+        }
+    }
+
+    datasource
+}
+
+/// Lower a `generator` block, keeping every property other than `provider` and `output` as an option
+fn lower_generator(block: &ConfigBlock, diagnostics: &mut Diagnostics) -> Generator {
+    let mut generator = Generator {
+        name: block.name.name.clone(),
+        provider: None,
+        output: None,
+        config: serde_json::Map::new(),
+    };
+
+    for property in &block.properties {
+        let Some(value) = generator_value(&property.value, diagnostics) else {
+            continue;
+        };
+        match (property.name.name.as_str(), value) {
+            ("provider", Value::String(provider)) => generator.provider = Some(provider),
+            ("output", Value::String(output)) => generator.output = Some(output),
+            ("provider" | "output", _) => diagnostics.push(Diagnostic::error(
+                format!("`{}` must be a string", property.name.name),
+                property.value.span(),
+            )),
+            (name, value) => {
+                generator.config.insert(name.to_string(), value);
+            }
+        }
+    }
+
+    generator
+}
+
+/// Generator properties are strings, lists of strings or `env("VAR")`
+fn generator_value(value: &Expression, diagnostics: &mut Diagnostics) -> Option<Value> {
+    match value {
+        Expression::String(value, _) => Some(Value::String(value.clone())),
+        Expression::Number(value, _) | Expression::Constant(value, _) => Some(Value::String(value.clone())),
+        Expression::Array(items, _) => items
+            .iter()
+            .map(|item| generator_value(item, diagnostics))
+            .collect::<Option<Vec<_>>>()
+            .map(Value::Array),
+        Expression::Function(name, arguments, span) if name == "env" => {
+            match arguments.first().and_then(|a| a.value.as_str()) {
+                Some(variable) => Some(std::env::var(variable).map(Value::String).unwrap_or(Value::Null)),
+                None => {
+                    diagnostics.push(
+                        Diagnostic::error("`env` expects the variable name as a string", *span)
+                            .with_help("for example `env(\"DATABASE_URL\")`"),
+                    );
+                    None
+                }
+            }
+        }
+        Expression::Function(name, _, span) => {
+            diagnostics.push(
+                Diagnostic::error(format!("unexpected function `{}` in generator block", name), *span)
+                    .with_help("generator options must be strings, lists of strings or `env(...)`"),
+            );
+            None
+        }
+    }
+}
+
+fn collect_declarations<'a>(asts: &'a [SchemaAst], diagnostics: &mut Diagnostics) -> Declarations<'a> {
+    let mut declarations = Declarations {
+        models: HashMap::new(),
+        enums: Vec::new(),
+        unsupported: HashMap::new(),
+        ignored_models: Vec::new(),
+        provider: None,
+    };
+    let mut seen: HashMap<&str, Span> = HashMap::new();
+
+    for top in asts.iter().flat_map(|ast| &ast.tops) {
+        let name = match top {
+            Top::Enum(e) => {
+                declarations.enums.push(&e.name.name);
+                &e.name
+            }
+            Top::Model(m) => {
+                if m.kind == ModelKind::Model {
+                    declarations.models.insert(&m.name.name, m);
+                    if find_attribute(&m.attributes, "ignore").is_some() {
+                        declarations.ignored_models.push(&m.name.name);
+                    }
+                } else {
+                    declarations.unsupported.insert(&m.name.name, m.kind);
+                }
+                &m.name
+            }
+            _ => continue,
+        };
+
+        if let Some(previous) = seen.insert(&name.name, name.span) {
+            diagnostics.push(
+                Diagnostic::error(format!("the name `{}` is defined more than once", name.name), name.span)
+                    .with_label("redefined here")
+                    .with_note(previous, "first defined here"),
+            );
+        }
+    }
+
+    declarations
+}
+
+fn lower_model(block: &ModelBlock, declarations: &Declarations, diagnostics: &mut Diagnostics) -> Option<Model> {
+    check_block_attributes(&block.attributes, diagnostics);
+
+    let mut fields = Vec::new();
+    let mut primary_key = None;
+    // Names of the fields in the GraphQL type, after `@pothos.name`
+    let mut output_names: HashMap<String, Span> = HashMap::new();
+
+    for decl in &block.fields {
+        if let Some(field) = lower_field(decl, block, declarations, diagnostics) {
+            if !field.directives.omit_output {
+                let output_name = field.directives.name.clone().unwrap_or_else(|| field.name.clone());
+                if let Some(previous) = output_names.insert(output_name.clone(), decl.name.span) {
+                    diagnostics.push(
+                        Diagnostic::error(
+                            format!("model `{}` has more than one field named `{}`", block.name.name, output_name),
+                            decl.name.span,
+                        )
+                        .with_label(match &field.directives.name {
+                            Some(_) => format!("`@pothos.name` renames `{}` to `{}`", field.name, output_name),
+                            None => "redefined here".to_string(),
+                        })
+                        .with_note(previous, "first used here")
+                        .with_help("pick another name in `@pothos.name` or hide one of the fields with `@pothos.omit(output)`"),
+                    );
+                }
+            }
+            if field.is_id && primary_key.is_none() {
+                primary_key = Some(PrimaryKey {
+                    fields: vec![field.name.clone()],
+                    name: None,
+                });
+            }
+            fields.push(field);
+        }
+    }
+
+    let mut db_name = None;
+    let mut unique_fields = Vec::new();
+    let mut indexes = Vec::new();
+
+    for attribute in &block.attributes {
+        match attribute.name.name.as_str() {
+            "id" => {
+                if let Some(field) = fields.iter().find(|f| f.is_id) {
+                    diagnostics.push(
+                        Diagnostic::error(
+                            format!("model `{}` defines both `@id` and `@@id`", block.name.name),
+                            attribute.span,
+                        )
+                        .with_help(format!("remove `@id` from `{}` or drop this attribute", field.name)),
+                    );
+                }
+                if let Some(index) = lower_index(attribute, block, diagnostics) {
+                    primary_key = Some(PrimaryKey {
+                        fields: index.fields,
+                        name: index.name,
+                    });
+                }
+            }
+            "unique" => unique_fields.extend(lower_index(attribute, block, diagnostics)),
+            "index" => indexes.extend(lower_index(attribute, block, diagnostics)),
+            "map" => match attribute.argument("name", true).map(|a| &a.value) {
+                Some(Expression::String(name, _)) => db_name = Some(name.clone()),
+                _ => diagnostics.push(
+                    Diagnostic::error("`@@map` expects the table name as a string", attribute.span)
+                        .with_help("for example `@@map(\"users\")`"),
+                ),
+            },
+            _ => {}
+        }
+    }
+
+    // `@@ignore` models are not part of Prisma Client
+    if declarations.ignored_models.contains(&block.name.name.as_str()) {
+        return None;
+    }
+
+    Some(Model {
+        name: block.name.name.clone(),
+        db_name,
+        fields,
+        primary_key,
+        unique_fields,
+        indexes,
+        documentation: documentation(&block.comments),
+    })
+}
+
+/// Lower `@@id`, `@@unique` or `@@index`: `([a, b])`, `(fields: [a, b], name: "ab")`...
+fn lower_index(attribute: &Attribute, model: &ModelBlock, diagnostics: &mut Diagnostics) -> Option<Index> {
+    let Some(fields) = attribute.argument("fields", true) else {
+        diagnostics.push(
+            Diagnostic::error(format!("`@@{}` needs a list of fields", attribute.name.name), attribute.span)
+                .with_help(format!("for example `@@{}([firstName, lastName])`", attribute.name.name)),
+        );
+        return None;
+    };
+
+    let items = match fields.value.as_array() {
+        Some(items) if !items.is_empty() && items.iter().all(|i| field_reference_name(i).is_some()) => items,
+        _ => {
+            diagnostics.push(
+                Diagnostic::error("expected a non-empty list of field names", fields.value.span())
+                    .with_help(format!("for example `@@{}([firstName, lastName])`", attribute.name.name)),
+            );
+            return None;
+        }
+    };
+    check_field_names(items, model, diagnostics);
+
+    let name = match attribute.argument("name", false) {
+        Some(argument) => match argument.value.as_str() {
+            Some(name) => Some(name.to_string()),
+            None => {
+                diagnostics.push(Diagnostic::error("`name` must be a string", argument.value.span()));
+                None
+            }
+        },
+        None => None,
+    };
+
+    Some(Index {
+        fields: field_references(&fields.value),
+        name,
+    })
+}
+
+fn lower_field(
+    decl: &FieldDecl,
+    model: &ModelBlock,
+    declarations: &Declarations,
+    diagnostics: &mut Diagnostics,
+) -> Option<Field> {
+    check_field_attributes(&decl.attributes, diagnostics);
+
+    // Unsupported and ignored columns are not available in Prisma Client
+    if decl.field_type.unsupported.is_some() || find_attribute(&decl.attributes, "ignore").is_some() {
+        return None;
+    }
+
+    let type_ref = &decl.field_type;
+    let type_name = type_ref.name.name.as_str();
+
+    let field_type = match type_name {
+        "String" => FieldType::String,
+        "Int" => FieldType::Int,
+        "Float" => FieldType::Float,
+        "Boolean" => FieldType::Boolean,
+        "DateTime" => FieldType::DateTime,
+        "Json" => FieldType::Json,
+        "Decimal" => FieldType::Decimal,
+        "BigInt" => FieldType::BigInt,
+        "Bytes" => FieldType::Bytes,
+        other if declarations.enums.contains(&other) => FieldType::Enum(other.to_string()),
+        other if declarations.ignored_models.contains(&other) => return None,
+        other if declarations.models.contains_key(other) => FieldType::Model(other.to_string()),
+        other => {
+            diagnostics.push(unknown_type(other, type_ref.name.span, declarations));
+            return None;
+        }
+    };
+
+    if let Some(provider) = declarations.provider {
+        if type_ref.arity == Arity::List && field_type.is_scalar() && !provider.supports_scalar_lists() {
+            diagnostics.push(
+                Diagnostic::error(
+                    format!("scalar lists are not supported by the `{}` provider", provider.name()),
+                    type_ref.span,
+                )
+                .with_label(format!("`{}[]` is a scalar list", type_name)),
+            );
+        }
+    }
+
+    let relation_attribute = find_attribute(&decl.attributes, "relation");
+    let relation = match &field_type {
+        FieldType::Model(related_model) => {
+            if let Some(attribute) = relation_attribute {
+                check_relation(attribute, model, declarations.models[related_model.as_str()], diagnostics);
+            }
+            Some(lower_relation(relation_attribute, related_model))
+        }
+        _ => {
+            if let Some(attribute) = relation_attribute {
+                diagnostics.push(
+                    Diagnostic::error("`@relation` is only valid on relation fields", attribute.span)
+                        .with_label(format!("`{}` is not a model", type_name)),
+                );
+            }
+            None
+        }
+    };
+
+    let default_value = find_attribute(&decl.attributes, "default")
+        .and_then(|a| a.argument("value", true))
+        .map(|a| a.value.to_string());
+
+    let (documentation, directives) = field_documentation(&decl.comments, diagnostics);
+
+    let is_required = type_ref.arity == Arity::Required;
+    let is_id = find_attribute(&decl.attributes, "id").is_some();
+    let is_updated_at = find_attribute(&decl.attributes, "updatedAt").is_some();
+    if directives.omit_create && is_required && !is_id && !is_updated_at && default_value.is_none() && relation.is_none() {
+        diagnostics.push(
+            Diagnostic::warning(
+                format!("`{}` is required but hidden from create inputs", decl.name.name),
+                decl.name.span,
+            )
+            .with_label(format!("creating a `{}` through the API will fail", model.name.name))
+            .with_help("add a `@default(...)` or make the field optional"),
+        );
+    }
+
+    Some(Field {
+        name: decl.name.name.clone(),
+        field_type,
+        is_required,
+        is_list: type_ref.arity == Arity::List,
+        is_id,
+        is_unique: find_attribute(&decl.attributes, "unique").is_some(),
+        is_updated_at,
+        default_value,
+        relation,
+        documentation,
+        directives,
+    })
+}
+
+fn unknown_type(name: &str, span: Span, declarations: &Declarations) -> Diagnostic {
+    if let Some(kind) = declarations.unsupported.get(name) {
+        let kind = if *kind == ModelKind::View { "view" } else { "composite type" };
+        return Diagnostic::error(format!("fields of {} `{}` are not supported", kind, name), span)
+            .with_label(format!("`{}` is a {}", name, kind));
+    }
+
+    let candidates = SCALAR_TYPES
+        .iter()
+        .copied()
+        .chain(declarations.enums.iter().copied())
+        .chain(declarations.models.keys().copied());
+
+    let diagnostic = Diagnostic::error(format!("unknown type `{}`", name), span)
+        .with_label("not a scalar type, model or enum");
+
+    match suggest(name, candidates) {
+        Some(candidate) => diagnostic.with_help(format!("did you mean `{}`?", candidate)),
+        None => diagnostic,
+    }
+}
+
+fn check_field_attributes(attributes: &[Attribute], diagnostics: &mut Diagnostics) {
+    for attribute in attributes {
+        let name = attribute.name.name.as_str();
+        // Native type attributes such as `@db.VarChar(255)` do not affect the generated code
+        if name.contains('.') || FIELD_ATTRIBUTES.contains(&name) {
+            continue;
+        }
+        diagnostics.push(unknown_attribute(attribute, "@", &FIELD_ATTRIBUTES));
+    }
+}
+
+fn check_block_attributes(attributes: &[Attribute], diagnostics: &mut Diagnostics) {
+    for attribute in attributes {
+        if !BLOCK_ATTRIBUTES.contains(&attribute.name.name.as_str()) {
+            diagnostics.push(unknown_attribute(attribute, "@@", &BLOCK_ATTRIBUTES));
+        }
+    }
+}
+
+fn unknown_attribute(attribute: &Attribute, prefix: &str, known: &[&'static str]) -> Diagnostic {
+    let diagnostic = Diagnostic::error(
+        format!("unknown attribute `{}{}`", prefix, attribute.name.name),
+        attribute.name.span,
+    );
+    match suggest(&attribute.name.name, known.iter().copied()) {
+        Some(candidate) => diagnostic.with_help(format!("did you mean `{}{}`?", prefix, candidate)),
+        None => diagnostic,
+    }
+}
+
+/// Validate the arguments of `@relation(...)` against both sides of the relation
+fn check_relation(attribute: &Attribute, model: &ModelBlock, related: &ModelBlock, diagnostics: &mut Diagnostics) {
+    let mut positional = 0;
+
+    for argument in &attribute.arguments {
+        let Some(name) = &argument.name else {
+            positional += 1;
+            if positional > 1 {
+                diagnostics.push(
+                    Diagnostic::error("`@relation` accepts only one unnamed argument", argument.span)
+                        .with_label("the relation name is already set"),
+                );
+            } else if argument.value.as_str().is_none() {
+                diagnostics.push(
+                    Diagnostic::error("the relation name must be a string", argument.value.span())
+                        .with_help("write it as `@relation(\"Name\")` or `@relation(name: \"Name\")`"),
+                );
+            }
+            continue;
+        };
+
+        match name.name.as_str() {
+            "name" | "map" => {
+                if argument.value.as_str().is_none() {
+                    diagnostics.push(
+                        Diagnostic::error(format!("`{}` must be a string", name.name), argument.value.span()),
+                    );
+                }
+            }
+            "fields" | "references" => {
+                let is_field_list = argument
+                    .value
+                    .as_array()
+                    .is_some_and(|items| items.iter().all(|i| i.as_constant().is_some()));
+                if !is_field_list {
+                    diagnostics.push(
+                        Diagnostic::error(format!("`{}` must be a list of field names", name.name), argument.value.span())
+                            .with_help(format!("for example `{}: [id]`", name.name)),
+                    );
+                }
+            }
+            "onDelete" | "onUpdate" => {
+                let action = argument.value.as_constant().unwrap_or("");
+                if !REFERENTIAL_ACTIONS.contains(&action) {
+                    diagnostics.push(
+                        Diagnostic::error(format!("invalid referential action for `{}`", name.name), argument.value.span())
+                            .with_help(format!("expected one of {}", REFERENTIAL_ACTIONS.join(", "))),
+                    );
+                }
+            }
+            other => {
+                let diagnostic = Diagnostic::error(format!("unknown argument `{}` in `@relation`", other), name.span);
+                diagnostics.push(match suggest(other, RELATION_ARGUMENTS) {
+                    Some(candidate) => diagnostic.with_help(format!("did you mean `{}`?", candidate)),
+                    None => diagnostic.with_help(format!("valid arguments are {}", RELATION_ARGUMENTS.join(", "))),
+                });
+            }
+        }
+    }
+
+    let fields = attribute.argument("fields", false);
+    let references = attribute.argument("references", false);
+
+    match (fields, references) {
+        (Some(fields), None) => diagnostics.push(
+            Diagnostic::error("`@relation` has `fields` but no `references`", fields.span)
+                .with_help("add `references: [...]` listing the referenced fields of the related model"),
+        ),
+        (None, Some(references)) => diagnostics.push(
+            Diagnostic::error("`@relation` has `references` but no `fields`", references.span)
+                .with_help("add `fields: [...]` listing the foreign key fields of this model"),
+        ),
+        (Some(fields), Some(references)) => {
+            let field_names = fields.value.as_array().unwrap_or_default();
+            let reference_names = references.value.as_array().unwrap_or_default();
+            if field_names.len() != reference_names.len() {
+                diagnostics.push(
+                    Diagnostic::error("`fields` and `references` must have the same length", attribute.span)
+                        .with_label(format!("{} field(s) but {} reference(s)", field_names.len(), reference_names.len())),
+                );
+            }
+            check_field_names(field_names, model, diagnostics);
+            check_field_names(reference_names, related, diagnostics);
+        }
+        (None, None) => {}
+    }
+}
+
+fn check_field_names(items: &[Expression], model: &ModelBlock, diagnostics: &mut Diagnostics) {
+    for item in items {
+        let Some(name) = field_reference_name(item) else {
+            continue;
+        };
+        if model.fields.iter().any(|f| f.name.name == name) {
+            continue;
+        }
+        let diagnostic = Diagnostic::error(format!("model `{}` has no field `{}`", model.name.name, name), item.span());
+        diagnostics.push(match suggest(name, model.fields.iter().map(|f| f.name.name.as_str())) {
+            Some(candidate) => diagnostic.with_help(format!("did you mean `{}`?", candidate)),
+            None => diagnostic,
+        });
+    }
+}
+
+fn lower_relation(attribute: Option<&Attribute>, related_model: &str) -> Relation {
+    let argument = |name: &str, positional: bool| attribute.and_then(|a| a.argument(name, positional));
+
+    Relation {
+        name: argument("name", true).and_then(|a| a.value.as_str()).map(String::from),
+        fields: argument("fields", false).map(|a| field_references(&a.value)).unwrap_or_default(),
+        references: argument("references", false).map(|a| field_references(&a.value)).unwrap_or_default(),
+        related_model: related_model.to_string(),
+        on_delete: argument("onDelete", false).and_then(|a| a.value.as_constant()).map(String::from),
+        on_update: argument("onUpdate", false).and_then(|a| a.value.as_constant()).map(String::from),
+    }
+}
+
+/// Join the `///` comments of a declaration; plain `//` comments are not documentation
+fn documentation(comments: &[Comment]) -> Option<String> {
+    let lines: Vec<&str> = comments.iter().filter(|c| c.is_doc).map(|c| c.text.as_str()).collect();
+    if lines.is_empty() {
+        None
+    } else {
+        Some(lines.join("\n"))
+    }
+}
+
+/// Doc comments of a field with their `@pothos.*` directives taken out
+fn field_documentation(comments: &[Comment], diagnostics: &mut Diagnostics) -> (Option<String>, FieldDirectives) {
+    let mut directives = FieldDirectives::default();
+    let mut lines = Vec::new();
+
+    for comment in comments.iter().filter(|c| c.is_doc) {
+        let (rest, errors) = apply_directives(&comment.text, &mut directives);
+        for error in errors {
+            let diagnostic = Diagnostic::error(error.message, comment.span);
+            diagnostics.push(match error.help {
+                Some(help) => diagnostic.with_help(help),
+                None => diagnostic,
+            });
+        }
+        if !rest.is_empty() {
+            lines.push(rest);
+        }
+    }
+
+    let documentation = if lines.is_empty() { None } else { Some(lines.join("\n")) };
+    (documentation, directives)
+}
+
+fn find_attribute<'a>(attributes: &'a [Attribute], name: &str) -> Option<&'a Attribute> {
+    attributes.iter().find(|a| a.name.name == name)
+}
+
+fn string_argument(attributes: &[Attribute], attribute: &str) -> Option<String> {
+    find_attribute(attributes, attribute)
+        .and_then(|a| a.argument("name", true))
+        .and_then(|a| a.value.as_str())
+        .map(String::from)
+}
+
+/// Field names from a list like `[userId, orgId]` or `[title(sort: Desc)]`
+fn field_references(value: &Expression) -> Vec<String> {
+    value
+        .as_array()
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|item| field_reference_name(item).map(String::from))
+        .collect()
+}
+
+fn field_reference_name(item: &Expression) -> Option<&str> {
+    match item {
+        Expression::Constant(name, _) | Expression::Function(name, _, _) => Some(name),
+        _ => None,
+    }
+}
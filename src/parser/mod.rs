@@ -1,4 +1,13 @@
-use anyhow::Result;
+pub mod ast;
+pub mod diagnostics;
+pub mod directives;
+mod files;
+pub mod grammar;
+pub mod lexer;
+mod lower;
+
+use diagnostics::{Diagnostics, SourceMap};
+pub use files::load_schema_files;
 use serde::{Deserialize, Serialize};
 
 /// Represents a parsed Prisma schema
@@ -6,6 +15,106 @@ use serde::{Deserialize, Serialize};
 pub struct ParsedSchema {
     pub models: Vec<Model>,
     pub enums: Vec<Enum>,
+    pub datasource: Option<Datasource>,
+    pub generators: Vec<Generator>,
+}
+
+impl ParsedSchema {
+    /// Whether the datasource provider supports a feature
+    /// Schemas without a datasource block (e.g. a split schema file) allow everything
+    pub fn supports(&self, feature: fn(Provider) -> bool) -> bool {
+        self.datasource.as_ref().is_none_or(|d| feature(d.provider))
+    }
+}
+
+/// The `datasource` block
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Datasource {
+    pub name: String,
+    pub provider: Provider,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Provider {
+    Postgresql,
+    Cockroachdb,
+    Mysql,
+    Sqlite,
+    Sqlserver,
+    Mongodb,
+}
+
+impl Provider {
+    pub const NAMES: [&'static str; 6] = ["postgresql", "cockroachdb", "mysql", "sqlite", "sqlserver", "mongodb"];
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "postgresql" | "postgres" => Some(Provider::Postgresql),
+            "cockroachdb" => Some(Provider::Cockroachdb),
+            "mysql" => Some(Provider::Mysql),
+            "sqlite" => Some(Provider::Sqlite),
+            "sqlserver" => Some(Provider::Sqlserver),
+            "mongodb" => Some(Provider::Mongodb),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Provider::Postgresql => "postgresql",
+            Provider::Cockroachdb => "cockroachdb",
+            Provider::Mysql => "mysql",
+            Provider::Sqlite => "sqlite",
+            Provider::Sqlserver => "sqlserver",
+            Provider::Mongodb => "mongodb",
+        }
+    }
+
+    /// `mode: insensitive` in string filters
+    pub fn supports_insensitive_mode(self) -> bool {
+        matches!(self, Provider::Postgresql | Provider::Cockroachdb | Provider::Mongodb)
+    }
+
+    /// `String[]`, `Int[]`... columns
+    pub fn supports_scalar_lists(self) -> bool {
+        matches!(self, Provider::Postgresql | Provider::Cockroachdb | Provider::Mongodb)
+    }
+
+    /// `path`, `string_contains`, `array_contains`... in Json filters
+    pub fn supports_json_filters(self) -> bool {
+        matches!(self, Provider::Postgresql | Provider::Cockroachdb | Provider::Mysql)
+    }
+
+    /// Json `path` as a list of keys; MySQL takes a JSON path string instead
+    pub fn supports_json_path_list(self) -> bool {
+        !matches!(self, Provider::Mysql)
+    }
+
+    /// `isSet` in filters of optional fields
+    pub fn supports_is_set(self) -> bool {
+        matches!(self, Provider::Mongodb)
+    }
+
+    /// `nulls: first | last` when sorting nullable fields
+    pub fn supports_nulls_order(self) -> bool {
+        !matches!(self, Provider::Mongodb)
+    }
+
+    /// `skipDuplicates` in `createMany`
+    pub fn supports_skip_duplicates(self) -> bool {
+        !matches!(self, Provider::Sqlite | Provider::Sqlserver | Provider::Mongodb)
+    }
+}
+
+/// A `generator` block
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Generator {
+    pub name: String,
+    pub provider: Option<String>,
+    pub output: Option<String>,
+    /// Every other property, as written in the schema
+    pub config: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -14,7 +123,12 @@ pub struct Model {
     pub db_name: Option<String>,
     pub fields: Vec<Field>,
     pub primary_key: Option<PrimaryKey>,
-    pub unique_fields: Vec<Vec<String>>,
+    /// Compound unique constraints from `@@unique`
+    pub unique_fields: Vec<Index>,
+    /// Indexes from `@@index`
+    pub indexes: Vec<Index>,
+    /// Text of the `///` comments above the model
+    pub documentation: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -28,6 +142,30 @@ pub struct Field {
     pub is_updated_at: bool,
     pub default_value: Option<String>,
     pub relation: Option<Relation>,
+    pub documentation: Option<String>,
+    pub directives: FieldDirectives,
+}
+
+impl Field {
+    /// Name of the field in the GraphQL output type
+    pub fn output_name(&self) -> &str {
+        self.directives.name.as_deref().unwrap_or(&self.name)
+    }
+}
+
+/// Per-field settings from `@pothos.*` directives in `///` comments
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FieldDirectives {
+    /// Hidden from the object type
+    pub omit_output: bool,
+    /// Hidden from CreateInput, CreateManyInput and the nested create inputs
+    pub omit_create: bool,
+    pub omit_update: bool,
+    /// Hidden from WhereInput and the unique selectors
+    pub omit_where: bool,
+    pub omit_order_by: bool,
+    /// GraphQL name of the output field, from `@pothos.name("...")`
+    pub name: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -46,38 +184,6 @@ pub enum FieldType {
 }
 
 impl FieldType {
-    pub fn to_graphql_type(&self) -> String {
-        match self {
-            FieldType::String => "String".to_string(),
-            FieldType::Int => "Int".to_string(),
-            FieldType::Float => "Float".to_string(),
-            FieldType::Boolean => "Boolean".to_string(),
-            FieldType::DateTime => "DateTime".to_string(),
-            FieldType::Json => "JSON".to_string(),
-            FieldType::Decimal => "Decimal".to_string(),
-            FieldType::BigInt => "BigInt".to_string(),
-            FieldType::Bytes => "Bytes".to_string(),
-            FieldType::Enum(name) => name.clone(),
-            FieldType::Model(name) => name.clone(),
-        }
-    }
-
-    pub fn to_typescript_type(&self) -> String {
-        match self {
-            FieldType::String => "string".to_string(),
-            FieldType::Int => "number".to_string(),
-            FieldType::Float => "number".to_string(),
-            FieldType::Boolean => "boolean".to_string(),
-            FieldType::DateTime => "Date".to_string(),
-            FieldType::Json => "Prisma.JsonValue".to_string(),
-            FieldType::Decimal => "Prisma.Decimal".to_string(),
-            FieldType::BigInt => "bigint".to_string(),
-            FieldType::Bytes => "Buffer".to_string(),
-            FieldType::Enum(name) => name.clone(),
-            FieldType::Model(name) => name.clone(),
-        }
-    }
-
     pub fn is_scalar(&self) -> bool {
         !matches!(self, FieldType::Model(_))
     }
@@ -99,6 +205,14 @@ pub struct PrimaryKey {
     pub name: Option<String>,
 }
 
+/// A `@@unique` or `@@index` definition
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Index {
+    pub fields: Vec<String>,
+    /// Client-facing name given with `name:`
+    pub name: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Enum {
     pub name: String,
@@ -109,277 +223,180 @@ pub struct Enum {
 pub struct EnumValue {
     pub name: String,
     pub db_name: Option<String>,
+    pub documentation: Option<String>,
 }
 
-/// Parse a Prisma schema string using a simple regex-based parser
-/// This avoids dependency on psl internals
-pub fn parse_schema(schema_content: &str) -> Result<ParsedSchema> {
-    let mut models = Vec::new();
-    let mut enums = Vec::new();
-
-    // First pass: collect all enum and model names for type resolution
-    let mut enum_names: Vec<String> = Vec::new();
-    let mut model_names: Vec<String> = Vec::new();
-
-    for line in schema_content.lines() {
-        let line = line.trim();
-        if line.starts_with("enum ") {
-            let enum_name = line
-                .strip_prefix("enum ")
-                .and_then(|s| s.strip_suffix(" {"))
-                .or_else(|| line.strip_prefix("enum ").map(|s| s.trim_end_matches('{')))
-                .map(|s| s.trim())
-                .unwrap_or("");
-            if !enum_name.is_empty() {
-                enum_names.push(enum_name.to_string());
-            }
-        } else if line.starts_with("model ") {
-            let model_name = line
-                .strip_prefix("model ")
-                .and_then(|s| s.strip_suffix(" {"))
-                .or_else(|| line.strip_prefix("model ").map(|s| s.trim_end_matches('{')))
-                .map(|s| s.trim())
-                .unwrap_or("");
-            if !model_name.is_empty() {
-                model_names.push(model_name.to_string());
-            }
+/// Parse every file of `sources` and merge them into one `ParsedSchema`
+/// Each file is tokenized and parsed into an AST, then all ASTs are lowered together so
+/// models and enums can reference declarations from other files. Problems are reported
+/// to `diagnostics`, and `None` is returned if any of them is an error
+pub fn parse_schema(sources: &SourceMap, diagnostics: &mut Diagnostics) -> Option<ParsedSchema> {
+    let mut asts = Vec::new();
+
+    for file in 0..sources.len() {
+        let content = &sources.file(file).content;
+        match lexer::tokenize(content, file).and_then(|tokens| grammar::parse(&tokens)) {
+            Ok(ast) => asts.push(ast),
+            Err(diagnostic) => diagnostics.push(diagnostic),
         }
     }
 
-    let lines: Vec<&str> = schema_content.lines().collect();
-    let mut i = 0;
-
-    while i < lines.len() {
-        let line = lines[i].trim();
-
-        // Parse enum
-        if line.starts_with("enum ") {
-            let enum_name = line
-                .strip_prefix("enum ")
-                .and_then(|s| s.strip_suffix(" {"))
-                .or_else(|| line.strip_prefix("enum ").map(|s| s.trim_end_matches('{')))
-                .map(|s| s.trim())
-                .unwrap_or("")
-                .to_string();
-
-            let mut values = Vec::new();
-            i += 1;
-
-            while i < lines.len() && !lines[i].trim().starts_with('}') {
-                let value_line = lines[i].trim();
-                if !value_line.is_empty() && !value_line.starts_with("//") {
-                    let value_name = value_line.split_whitespace().next().unwrap_or("").to_string();
-                    if !value_name.is_empty() {
-                        values.push(EnumValue {
-                            name: value_name,
-                            db_name: None,
-                        });
-                    }
-                }
-                i += 1;
-            }
-
-            if !enum_name.is_empty() {
-                enums.push(Enum {
-                    name: enum_name,
-                    values,
-                });
-            }
-        }
+    if diagnostics.has_errors() {
+        return None;
+    }
 
-        // Parse model
-        if line.starts_with("model ") {
-            let model_name = line
-                .strip_prefix("model ")
-                .and_then(|s| s.strip_suffix(" {"))
-                .or_else(|| line.strip_prefix("model ").map(|s| s.trim_end_matches('{')))
-                .map(|s| s.trim())
-                .unwrap_or("")
-                .to_string();
-
-            let mut fields = Vec::new();
-            let mut primary_key = None;
-            i += 1;
-
-            while i < lines.len() && !lines[i].trim().starts_with('}') {
-                let field_line = lines[i].trim();
-
-                // Skip empty lines, comments, and block attributes
-                if field_line.is_empty() || field_line.starts_with("//") || field_line.starts_with("@@") {
-                    i += 1;
-                    continue;
-                }
-
-                if let Some(field) = parse_field(field_line, &model_name, &enum_names, &model_names) {
-                    if field.is_id && primary_key.is_none() {
-                        primary_key = Some(PrimaryKey {
-                            fields: vec![field.name.clone()],
-                            name: None,
-                        });
-                    }
-                    fields.push(field);
-                }
-
-                i += 1;
-            }
-
-            if !model_name.is_empty() {
-                models.push(Model {
-                    name: model_name,
-                    db_name: None,
-                    fields,
-                    primary_key,
-                    unique_fields: Vec::new(),
-                });
-            }
-        }
+    let schema = lower::lower_schema(&asts, diagnostics);
 
-        i += 1;
+    if diagnostics.has_errors() {
+        None
+    } else {
+        Some(schema)
     }
-
-    Ok(ParsedSchema { models, enums })
 }
 
-fn parse_field(line: &str, _model_name: &str, enum_names: &[String], model_names: &[String]) -> Option<Field> {
-    let parts: Vec<&str> = line.split_whitespace().collect();
-    if parts.len() < 2 {
-        return None;
-    }
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    let name = parts[0].to_string();
-    let type_str = parts[1];
-
-    // Parse type
-    let is_list = type_str.ends_with("[]");
-    let is_optional = type_str.ends_with('?');
-    let is_required = !is_optional && !is_list;
-
-    let base_type = type_str
-        .trim_end_matches("[]")
-        .trim_end_matches('?')
-        .to_string();
-
-    let (field_type, relation) = match base_type.as_str() {
-        "String" => (FieldType::String, None),
-        "Int" => (FieldType::Int, None),
-        "Float" => (FieldType::Float, None),
-        "Boolean" => (FieldType::Boolean, None),
-        "DateTime" => (FieldType::DateTime, None),
-        "Json" => (FieldType::Json, None),
-        "Decimal" => (FieldType::Decimal, None),
-        "BigInt" => (FieldType::BigInt, None),
-        "Bytes" => (FieldType::Bytes, None),
-        other => {
-            // Check if it's a known enum
-            if enum_names.contains(&other.to_string()) {
-                (FieldType::Enum(other.to_string()), None)
-            }
-            // Check if it's a known model (relation)
-            else if model_names.contains(&other.to_string()) {
-                // It's a relation to another model
-                let relation = parse_relation(line, other);
-                (FieldType::Model(other.to_string()), Some(relation))
-            }
-            // Unknown type - treat as String
-            else {
-                (FieldType::String, None)
-            }
+    /// Parse `files` as one schema, returning the schema and the rendered diagnostics
+    fn parse(files: &[(&str, &str)]) -> (Option<ParsedSchema>, String) {
+        let mut sources = SourceMap::new();
+        for (name, content) in files {
+            sources.add(*name, *content);
         }
-    };
-
-    // Parse attributes
-    let is_id = line.contains("@id");
-    let is_unique = line.contains("@unique");
-    let is_updated_at = line.contains("@updatedAt");
-
-    // Parse default value
-    let default_value = if line.contains("@default") {
-        let start = line.find("@default(").map(|i| i + 9);
-        let end = start.and_then(|s| line[s..].find(')').map(|e| s + e));
-        start.and_then(|s| end.map(|e| line[s..e].to_string()))
-    } else {
-        None
-    };
-
-    Some(Field {
-        name,
-        field_type,
-        is_required,
-        is_list,
-        is_id,
-        is_unique,
-        is_updated_at,
-        default_value,
-        relation,
-    })
+        let mut diagnostics = Diagnostics::new();
+        let schema = parse_schema(&sources, &mut diagnostics);
+        (schema, diagnostics.render(&sources))
+    }
+
+    #[test]
+    fn parses_valid_schema() {
+        let (schema, output) = parse(&[(
+            "schema.prisma",
+            r#"
+datasource db {
+  provider = "postgresql"
+  url      = env("DATABASE_URL")
 }
 
-fn parse_relation(line: &str, related_model: &str) -> Relation {
-    let mut fields = Vec::new();
-    let mut references = Vec::new();
-    let mut relation_name = None;
-
-    // Parse @relation(...)
-    if let Some(start) = line.find("@relation(") {
-        let content = &line[start + 10..];
-        if let Some(end) = content.find(')') {
-            let relation_content = &content[..end];
-
-            // Parse name
-            if let Some(name_start) = relation_content.find("name:") {
-                let name_part = &relation_content[name_start + 5..];
-                let name = name_part
-                    .trim()
-                    .trim_start_matches('"')
-                    .split('"')
-                    .next()
-                    .map(|s| s.to_string());
-                relation_name = name;
-            } else if let Some(name_start) = relation_content.find('"') {
-                let name_part = &relation_content[name_start + 1..];
-                if let Some(name_end) = name_part.find('"') {
-                    relation_name = Some(name_part[..name_end].to_string());
-                }
-            }
-
-            // Parse fields
-            if let Some(fields_start) = relation_content.find("fields:") {
-                let fields_part = &relation_content[fields_start + 7..];
-                if let Some(bracket_start) = fields_part.find('[') {
-                    let fields_content = &fields_part[bracket_start + 1..];
-                    if let Some(bracket_end) = fields_content.find(']') {
-                        fields = fields_content[..bracket_end]
-                            .split(',')
-                            .map(|s| s.trim().to_string())
-                            .filter(|s| !s.is_empty())
-                            .collect();
-                    }
-                }
-            }
-
-            // Parse references
-            if let Some(refs_start) = relation_content.find("references:") {
-                let refs_part = &relation_content[refs_start + 11..];
-                if let Some(bracket_start) = refs_part.find('[') {
-                    let refs_content = &refs_part[bracket_start + 1..];
-                    if let Some(bracket_end) = refs_content.find(']') {
-                        references = refs_content[..bracket_end]
-                            .split(',')
-                            .map(|s| s.trim().to_string())
-                            .filter(|s| !s.is_empty())
-                            .collect();
-                    }
-                }
-            }
-        }
+generator client {
+  provider = "prisma-client-js"
+  output   = "../generated"
+}
+
+enum Role {
+  USER
+  ADMIN @map("admin")
+}
+
+/// A user
+model User {
+  id    Int     @id @default(autoincrement())
+  email String  @unique
+  name  String?
+  role  Role    @default(USER)
+  posts Post[]
+}
+
+model Post {
+  id       Int  @id
+  authorId Int
+  author   User @relation(fields: [authorId], references: [id], onDelete: Cascade)
+}
+"#,
+        )]);
+        let schema = schema.unwrap_or_else(|| panic!("{}", output));
+
+        let datasource = schema.datasource.unwrap();
+        assert_eq!(datasource.name, "db");
+        assert_eq!(datasource.provider, Provider::Postgresql);
+
+        assert_eq!(schema.generators.len(), 1);
+        assert_eq!(schema.generators[0].provider.as_deref(), Some("prisma-client-js"));
+        assert_eq!(schema.generators[0].output.as_deref(), Some("../generated"));
+
+        assert_eq!(schema.enums.len(), 1);
+        let values: Vec<_> = schema.enums[0].values.iter().map(|v| v.name.as_str()).collect();
+        assert_eq!(values, ["USER", "ADMIN"]);
+        assert_eq!(schema.enums[0].values[1].db_name.as_deref(), Some("admin"));
+
+        let user = &schema.models[0];
+        assert_eq!(user.name, "User");
+        assert_eq!(user.documentation.as_deref(), Some("A user"));
+        assert!(user.fields[0].is_id);
+        assert!(user.fields[1].is_unique);
+        assert!(!user.fields[2].is_required);
+        assert!(matches!(&user.fields[3].field_type, FieldType::Enum(name) if name == "Role"));
+        assert!(user.fields[4].is_list);
+
+        let relation = schema.models[1].fields[2].relation.as_ref().unwrap();
+        assert_eq!(relation.related_model, "User");
+        assert_eq!(relation.fields, ["authorId"]);
+        assert_eq!(relation.references, ["id"]);
+        assert_eq!(relation.on_delete.as_deref(), Some("Cascade"));
+    }
+
+    #[test]
+    fn reports_unterminated_block() {
+        let (schema, output) = parse(&[("schema.prisma", "model User {\n  id Int @id\n")]);
+        assert!(schema.is_none());
+        assert!(output.contains("unterminated block `User`"), "{}", output);
+    }
+
+    #[test]
+    fn reports_unknown_attribute() {
+        let (schema, output) = parse(&[("schema.prisma", "model User {\n  id Int @id @uniqe\n}\n")]);
+        assert!(schema.is_none());
+        assert!(output.contains("unknown attribute `@uniqe`"), "{}", output);
+        assert!(output.contains("did you mean `@unique`?"), "{}", output);
+    }
+
+    #[test]
+    fn reports_unknown_type_with_suggestion() {
+        let (schema, output) = parse(&[("schema.prisma", "model User {\n  id Int @id\n  name Strng\n}\n")]);
+        assert!(schema.is_none());
+        assert!(output.contains("unknown type `Strng`"), "{}", output);
+        assert!(output.contains("schema.prisma:3:8"), "{}", output);
+        assert!(output.contains("did you mean `String`?"), "{}", output);
+    }
+
+    #[test]
+    fn resolves_types_across_files() {
+        let (schema, output) = parse(&[
+            ("user.prisma", "model User {\n  id Int @id\n  role Role\n  posts Post[]\n}\n"),
+            (
+                "post.prisma",
+                "model Post {\n  id Int @id\n  authorId Int\n  author User @relation(fields: [authorId], references: [id])\n}\n",
+            ),
+            ("enums.prisma", "enum Role {\n  USER\n  ADMIN\n}\n"),
+        ]);
+        let schema = schema.unwrap_or_else(|| panic!("{}", output));
+
+        assert_eq!(schema.models.len(), 2);
+        assert!(matches!(&schema.models[0].fields[1].field_type, FieldType::Enum(name) if name == "Role"));
+        assert!(matches!(&schema.models[0].fields[2].field_type, FieldType::Model(name) if name == "Post"));
+        assert_eq!(schema.models[1].fields[2].relation.as_ref().unwrap().related_model, "User");
+    }
+
+    #[test]
+    fn reports_errors_in_the_right_file() {
+        let (schema, output) = parse(&[
+            ("user.prisma", "model User {\n  id Int @id\n}\n"),
+            ("post.prisma", "model Post {\n  id Int @id\n  author Usr\n}\n"),
+        ]);
+        assert!(schema.is_none());
+        assert!(output.contains("post.prisma:3:10"), "{}", output);
+        assert!(output.contains("did you mean `User`?"), "{}", output);
     }
 
-    Relation {
-        name: relation_name,
-        fields,
-        references,
-        related_model: related_model.to_string(),
-        on_delete: None,
-        on_update: None,
+    #[test]
+    fn reports_colliding_field_rename() {
+        let (schema, output) = parse(&[(
+            "schema.prisma",
+            "model User {\n  id Int @id\n  login String\n  /// @pothos.name(\"login\")\n  email String\n}\n",
+        )]);
+        assert!(schema.is_none());
+        assert!(output.contains("model `User` has more than one field named `login`"), "{}", output);
+        assert!(output.contains("schema.prisma:5:3"), "{}", output);
     }
 }